@@ -0,0 +1,102 @@
+//! Baseline of accepted findings (`--baseline FILE`), so CI only fails on new dead code.
+//!
+//! Entries are keyed by category, name, root-relative file and a hash of the definition line's
+//! text rather than its line number, so edits above a function do not churn the baseline.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::report::{uri_path, Category};
+use crate::scanner::{fnv1a, SourceLines};
+
+/// Format version written to the file.
+const BASELINE_VERSION: u32 = 1;
+
+/// One accepted finding.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// Rule id of the finding's category (e.g. `unused-function`).
+    pub rule: String,
+    pub name: String,
+    /// Path relative to the scan root, `/`-separated.
+    pub file: String,
+    /// FNV-1a hash (hex) of the trimmed definition line.
+    pub hash: String,
+}
+
+/// Set of accepted findings, stored as JSON.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    pub entries: Vec<BaselineEntry>,
+}
+
+/// Builds [`BaselineEntry`]s for findings under one root, reading each source file once and
+/// decoding it like the scan does (so non-UTF-8 scripts hash their real definition line).
+pub struct EntryBuilder<'a> {
+    root: &'a Path,
+    sources: SourceLines,
+}
+
+impl<'a> EntryBuilder<'a> {
+    pub fn new(root: &'a Path) -> Self {
+        EntryBuilder {
+            root,
+            sources: SourceLines::default(),
+        }
+    }
+
+    /// The entry for a finding of `category` at `file:line` (absolute path under the root).
+    pub fn entry(
+        &mut self,
+        category: Category,
+        name: &str,
+        file: &Path,
+        line: u32,
+    ) -> BaselineEntry {
+        let def_line = self.sources.line(file, line).unwrap_or("");
+        BaselineEntry {
+            rule: category.rule_id().to_string(),
+            name: name.to_string(),
+            file: uri_path(file.strip_prefix(self.root).unwrap_or(file)),
+            hash: format!("{:016x}", fnv1a(def_line.as_bytes())),
+        }
+    }
+}
+
+impl Baseline {
+    pub fn new(entries: Vec<BaselineEntry>) -> Self {
+        Baseline {
+            version: BASELINE_VERSION,
+            entries,
+        }
+    }
+
+    /// Read a baseline file; the error message names the file.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read baseline {}: {}", path.display(), e))?;
+        serde_json::from_str(&text)
+            .map_err(|e| format!("invalid baseline {}: {}", path.display(), e))
+    }
+
+    /// Write the baseline as pretty-printed JSON.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let text = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, text + "\n")
+    }
+
+    /// Keep only the entries `keep` accepts; returns how many were removed.
+    pub fn retain(&mut self, keep: impl FnMut(&BaselineEntry) -> bool) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(keep);
+        before - self.entries.len()
+    }
+
+    /// Lookup set for filtering findings.
+    pub fn entry_set(&self) -> HashSet<&BaselineEntry> {
+        self.entries.iter().collect()
+    }
+}
@@ -1,17 +1,143 @@
 //! CLI: args, path display, and run logic.
 
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{BufRead, IsTerminal, Write};
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
 
 /// Max number of directory entries to list in verbose mode before truncating.
 const VERBOSE_LIST_MAX: usize = 50;
 
+/// Per-project ignore file, read from the scan root.
+const GDCF_IGNORE_FILE: &str = ".gdcfignore";
+
+use crate::baseline::{Baseline, EntryBuilder};
+use crate::config::{Config, DEFAULT_CONFIG_FILE};
+use crate::report::{github, json, junit, sarif, Category, Finding, Report, Severity};
+use crate::scanner::project_godot::{ProjectConfig, PROJECT_FILE};
+use crate::scanner::util::edit_distance;
 use crate::scanner::{
-    default_is_test_path, find_only_test_referenced_functions, find_unused_functions,
-    iter_gd_files, iter_tscn_files, scan_directory, FunctionDef, ScanResult,
+    always_used_reason, counted_references, default_is_test_path, find_duplicate_definitions,
+    find_function_references, find_only_test_referenced_functions_with, find_unreachable_functions,
+    find_unused_consts, find_unused_enum_members, find_unused_functions_with, find_unused_signals,
+    gdscript_suffixes, is_glob_pattern, is_walked_path, iter_files_by_extension,
+    scan_directory_with_progress, AnalysisOptions, Confidence, ConstDef, EnumMemberDef,
+    FunctionDef, IgnorePatterns, ScanCache, ScanOptions, ScanResult, SharedTestPathFn, SignalDef,
+    SourceLines, TestFramework, WalkOptions, DEFAULT_NOREF_PATTERN,
 };
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use notify::{EventKind, RecursiveMode, Watcher};
+use serde::Deserialize;
+
+/// Output format for findings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Human-readable list (default)
+    #[default]
+    Text,
+    /// SARIF 2.1.0 JSON (e.g. for GitHub code scanning)
+    Sarif,
+    /// GitHub Actions `::warning` annotations (inline on the PR diff)
+    Github,
+    /// Plain JSON findings (can be compared later with `--compare`)
+    Json,
+    /// JUnit XML (each finding a failed test case, for test-report dashboards)
+    Junit,
+}
+
+/// Order of the reported functions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum SortKey {
+    /// By file, then line (default)
+    #[default]
+    File,
+    /// By line, then file
+    Line,
+    /// By function name, then file and line
+    Name,
+    /// Longest functions (most lines) first
+    Size,
+}
+
+/// Which findings make the run exit with code 1.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum FailOn {
+    /// Never: always exit 0 (informational runs)
+    None,
+    /// Unused functions, signals, constants or enum members
+    Unused,
+    /// Functions only called from test code
+    TestOnly,
+    /// Any finding (default)
+    #[default]
+    Any,
+}
+
+impl FailOn {
+    /// Whether findings of `category` fail the run.
+    fn fails(self, category: Category) -> bool {
+        match self {
+            FailOn::None => false,
+            FailOn::Unused => category != Category::TestOnly,
+            FailOn::TestOnly => category == Category::TestOnly,
+            FailOn::Any => true,
+        }
+    }
+}
+
+/// A `--severity` entry: findings of `categories` are reported at `severity`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SeverityRule {
+    pub categories: Vec<Category>,
+    pub severity: Severity,
+}
 
-#[derive(Parser)]
+/// Parse `KIND=LEVEL`, where KIND is `unused` (every never-referenced kind), `test-only` or a rule
+/// id such as `unused-signal`, and LEVEL is `error`, `warning` (`warn`) or `note`.
+fn parse_severity_rule(s: &str) -> Result<SeverityRule, String> {
+    let (kind, level) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected KIND=LEVEL, got `{}`", s))?;
+    let categories = match kind.trim() {
+        "unused" => vec![
+            Category::Unused,
+            Category::UnusedSignal,
+            Category::UnusedConst,
+            Category::UnusedEnumMember,
+        ],
+        "test-only" => vec![Category::TestOnly],
+        id => match Category::ALL.iter().find(|c| c.rule_id() == id) {
+            Some(c) => vec![*c],
+            None => return Err(format!("unknown finding kind `{}`", id)),
+        },
+    };
+    let severity = Severity::from_name(level.trim())
+        .ok_or_else(|| format!("unknown severity `{}` (error, warning, note)", level.trim()))?;
+    Ok(SeverityRule {
+        categories,
+        severity,
+    })
+}
+
+/// A result section for `--show`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Section {
+    /// Unused functions
+    Functions,
+    /// Functions only called from test code
+    TestOnly,
+    /// Unused signals (`--signals`)
+    Signals,
+    /// Unused constants (`--consts`)
+    Consts,
+    /// Unused enum members (`--enums`)
+    Enums,
+}
+
+#[derive(Clone, Parser)]
 #[command(name = "godot-dead-code")]
 #[command(about = "Find functions that are never called in a Godot GDScript codebase.")]
 #[command(version = env!("CARGO_PKG_VERSION"))]
@@ -20,11 +146,21 @@ pub struct Args {
     #[arg(value_name = "PATH")]
     pub path: Option<PathBuf>,
 
-    /// Only exit with code 1 if unused or test-only functions found; no output
+    /// Only exit with code 1 if unused or test-only functions found; no output (an `--output`
+    /// file is still written)
     #[arg(short, long)]
     pub quiet: bool,
 
-    /// Dir (relative to root) treated as test code; repeatable (default: tests/, test/, *_test.gd, test_*.gd)
+    /// Undo `quiet = true` from the config file
+    #[arg(long, overrides_with = "quiet")]
+    pub no_quiet: bool,
+
+    /// Print the report only when there are findings; print nothing when the project is clean
+    /// (an `--output` file is still written)
+    #[arg(long)]
+    pub quiet_if_clean: bool,
+
+    /// Dir (relative to root) or path glob (e.g. **/*_spec.gd) treated as test code; repeatable (default: tests/, test/, *_test.gd, test_*.gd)
     #[arg(long = "test-dir", value_name = "DIR")]
     pub test_dirs: Vec<String>,
 
@@ -36,17 +172,278 @@ pub struct Args {
     #[arg(short, long, action = clap::ArgAction::Count)]
     pub verbose: u8,
 
-    /// Directory name (or **/name) to exclude from scan; can be repeated (default when omitted: **/addons)
+    /// Directory name (or **/name) or path glob (e.g. src/**/generated) to exclude from scan; can be repeated (default when omitted: **/addons, **/.godot, **/.import)
     #[arg(long = "exclude-dir", value_name = "DIR")]
     pub exclude_dirs: Vec<String>,
 
-    /// Do not add default exclude (**/addons); use only --exclude-dir values (or none)
+    /// Path glob (gitignore-style, relative to root) whose functions are never reported as unused;
+    /// they are still scanned, and test-only functions there are still reported. Can be repeated
+    #[arg(long = "exclude-unused-in", value_name = "GLOB")]
+    pub exclude_unused_in: Vec<String>,
+
+    /// Only report findings in files under DIR (relative to root), e.g. the directories a PR
+    /// touches; the whole project is still scanned for references. Can be repeated
+    #[arg(long = "changed-path", value_name = "DIR")]
+    pub changed_paths: Vec<PathBuf>,
+
+    /// Read newline-separated file paths (relative to root, e.g. from `git diff --name-only`)
+    /// from stdin and only report findings in those .gd files; other paths are ignored
+    #[arg(long)]
+    pub stdin_files: bool,
+
+    /// Extra extension (without the dot) of files scanned as GDScript besides `.gd`, e.g.
+    /// `gdscript`; can be repeated
+    #[arg(long = "ext", value_name = "EXT")]
+    pub extensions: Vec<String>,
+
+    /// Keep running: rescan and reprint the results (clearing the screen on a terminal) whenever a
+    /// scanned GDScript, .tscn or .tres file changes (excluded and ignored paths do not count);
+    /// stop with Ctrl-C
+    #[arg(long)]
+    pub watch: bool,
+
+    /// Do not add default excludes (**/addons, **/.godot, **/.import); --exclude-dir, config and .gdcfignore excludes still apply
     #[arg(long)]
     pub no_default_excludes: bool,
 
+    /// Undo `no_default_excludes = true` from the config file
+    #[arg(long, overrides_with = "no_default_excludes")]
+    pub default_excludes: bool,
+
+    /// Walk into symlinked directories (default: skip them); each real file is scanned once
+    #[arg(long)]
+    pub follow_symlinks: bool,
+
+    /// Walk into hidden directories like .git or .vscode (default: skip them); .godot and .import
+    /// stay excluded unless --no-default-excludes or --exclude-dir replace the defaults
+    #[arg(long)]
+    pub include_hidden: bool,
+
     /// Debug mode: show all references found for a specific function name
     #[arg(long, value_name = "NAME")]
     pub debug_function: Option<String>,
+
+    /// Scope-aware matching: bare calls only count for definitions in the same file, its
+    /// `extends` ancestors, or scripts it names via class_name/preload
+    #[arg(long)]
+    pub scoped: bool,
+
+    /// Undo `scoped = true` from the config file
+    #[arg(long, overrides_with = "scoped")]
+    pub no_scoped: bool,
+
+    /// Report functions not reachable from an entry point (engine callbacks, scenes, script-level
+    /// code, tests): also catches dead functions that only call each other
+    #[arg(long)]
+    pub reachable: bool,
+
+    /// Cache per-file scan results in DIR (default: .gdcf-cache under the root); invalidated when
+    /// the analysis config changes
+    #[arg(long, value_name = "DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Parse every file and leave the cache untouched
+    #[arg(long, conflicts_with = "cache_dir")]
+    pub no_cache: bool,
+
+    /// Delete the cache before scanning (it is rebuilt by this run unless --no-cache)
+    #[arg(long)]
+    pub clear_cache: bool,
+
+    /// Print at most N sites per function name, then a "(+K more)" suffix (text output)
+    #[arg(long, value_name = "N")]
+    pub findings_limit_per_name: Option<NonZeroUsize>,
+
+    /// Print the `func` line (trimmed) under each reported function (text output)
+    #[arg(long)]
+    pub with_signature: bool,
+
+    /// Print each file once as a header, with its functions as indented `line: name` entries
+    /// (text output)
+    #[arg(long)]
+    pub group_by_file: bool,
+
+    /// Function called from native code (GDExtension/GDNative callback); never reported. Can be repeated
+    #[arg(long = "native-callback", value_name = "NAME")]
+    pub native_callbacks: Vec<String>,
+
+    /// Project virtual method invoked by a framework base class (e.g. `_on_spawn`); treated as used
+    /// like the built-in engine callbacks. Can be repeated
+    #[arg(long = "virtual", value_name = "NAME")]
+    pub virtual_methods: Vec<String>,
+
+    /// Never report unused functions defined in test code (test-only references are still reported)
+    #[arg(long)]
+    pub skip_test_definitions: bool,
+
+    /// Only count references at least this certain: `exact` ignores identifiers matched as call
+    /// arguments or assigned values (default: every reference counts)
+    #[arg(long, value_enum, value_name = "LEVEL")]
+    pub min_confidence: Option<Confidence>,
+
+    /// Test framework whose `test_*` methods and lifecycle hooks are entry points: `gut`
+    /// (before_each/after_each/...), `gdunit4` (before/after/before_test/after_test) or `both`
+    #[arg(long, value_enum, value_name = "NAME", default_value_t = TestFramework::Both)]
+    pub test_framework: TestFramework,
+
+    /// Only exempt `test_*` methods defined in test code (lifecycle hooks still count anywhere),
+    /// so unused `test_*` helpers in regular scripts are reported
+    #[arg(long)]
+    pub strict_tests: bool,
+
+    /// Treat PREFIX<action> functions as used for every input action in project.godot's [input]
+    /// section (e.g. `_on_action_` keeps `_on_action_jump` alive)
+    #[arg(long, value_name = "PREFIX")]
+    pub input_action_prefix: Option<String>,
+
+    /// Markdown files (glob relative to the root, e.g. `docs/**/*.md`) whose ```gdscript / ```gd
+    /// fenced blocks count as references; can be repeated
+    #[arg(long, value_name = "GLOB")]
+    pub doc_refs: Vec<String>,
+
+    /// Files marked `# gdcf-ignore-file` contribute no references either (by default their calls
+    /// still count; only their definitions are skipped)
+    #[arg(long)]
+    pub no_ignored_file_refs: bool,
+
+    /// Godot project file to read autoloads, the main scene and input actions from (default:
+    /// project.godot at the scan root, when present)
+    #[arg(long, value_name = "FILE")]
+    pub project: Option<PathBuf>,
+
+    /// Read autoloads from project.godot so `Singleton.method()` only counts for the singleton
+    /// script's `method`, not for every `method` in the project
+    #[arg(long)]
+    pub autoloads: bool,
+
+    /// Undo `autoloads = true` from the config file
+    #[arg(long, overrides_with = "autoloads")]
+    pub no_autoloads: bool,
+
+    /// Warn (on stderr) about calls whose target cannot be resolved, like
+    /// `data.get("handler").call()`; such calls credit no function
+    #[arg(long)]
+    pub warn_dynamic: bool,
+
+    /// Do not scan .tres resources for method names stored as strings
+    #[arg(long)]
+    pub no_tres: bool,
+
+    /// .tscn node property whose string value names a method (e.g. an exported `callback_name`
+    /// set in the inspector); can be repeated
+    #[arg(long = "tscn-ref-property", value_name = "NAME")]
+    pub tscn_ref_properties: Vec<String>,
+
+    /// Regex for source lines whose identifiers are not references (default: `# gdcf-noref`
+    /// comment), for one-off false positives
+    #[arg(long, value_name = "REGEX")]
+    pub noref_pattern: Option<String>,
+
+    /// Also report `signal` declarations that are never emitted or connected
+    #[arg(long)]
+    pub signals: bool,
+
+    /// Undo `signals = true` from the config file
+    #[arg(long, overrides_with = "signals")]
+    pub no_signals: bool,
+
+    /// Also report `const` declarations that are never used
+    #[arg(long)]
+    pub consts: bool,
+
+    /// Undo `consts = true` from the config file
+    #[arg(long, overrides_with = "consts")]
+    pub no_consts: bool,
+
+    /// Also report enum members (named or anonymous enums) that are never used
+    #[arg(long)]
+    pub enums: bool,
+
+    /// Undo `enums = true` from the config file
+    #[arg(long, overrides_with = "enums")]
+    pub no_enums: bool,
+
+    /// Explain why NAME is unused: confirm it has no references and list near-miss references
+    /// to similarly named functions (edit distance 1), e.g. typo'd calls
+    #[arg(long, value_name = "NAME")]
+    pub explain_unused: Option<String>,
+
+    /// For each definition of NAME, print whether it is reported (unused, test-only) or used and
+    /// why (engine callback, test hook, ignore tag, @rpc, the references that keep it alive), then
+    /// exit
+    #[arg(long, value_name = "NAME")]
+    pub explain: Option<String>,
+
+    /// List function names defined in more than one file (e.g. a helper pasted into two
+    /// autoloads) with every file:line site, then exit (1 if any)
+    #[arg(long)]
+    pub duplicates: bool,
+
+    /// Remove PREFIX from every emitted path (e.g. the CI checkout dir /github/workspace)
+    #[arg(long, value_name = "PREFIX")]
+    pub strip_prefix: Option<PathBuf>,
+
+    /// Order of reported functions in every output format: `file` (then line), `line`, `name`,
+    /// or `size` (longest, i.e. most dead lines, first)
+    #[arg(long, value_enum, value_name = "KEY", default_value_t = SortKey::File)]
+    pub sort: SortKey,
+
+    /// Output format (default when omitted: text)
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    pub format: Option<OutputFormat>,
+
+    /// Write the report (any format) to FILE instead of stdout; parent directories are created
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Also write one JSON report per script with findings to DIR/<path relative to root>.json
+    /// (same layout as `--format json`), e.g. for editor diagnostics
+    #[arg(long, value_name = "DIR")]
+    pub output_per_file: Option<PathBuf>,
+
+    /// Compare two reports written with `--format json` without scanning: print findings added in
+    /// NEW and removed since OLD, exit 1 if any were added
+    #[arg(long, num_args = 2, value_names = ["OLD", "NEW"])]
+    pub compare: Option<Vec<PathBuf>>,
+
+    /// Which findings fail the run (exit 1): `none`, `unused`, `test-only` or `any`
+    #[arg(long, value_enum, value_name = "WHEN", default_value_t = FailOn::Any)]
+    pub fail_on: FailOn,
+
+    /// Severity per finding kind in reports (comma-separated KIND=LEVEL, e.g.
+    /// `unused=error,test-only=warn`; default: unused error, test-only warning); when given, the
+    /// run fails only on error-severity findings
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "KIND=LEVEL,...",
+        value_parser = parse_severity_rule,
+        conflicts_with = "fail_on"
+    )]
+    pub severity: Vec<SeverityRule>,
+
+    /// Only print these result sections (comma-separated: functions, test-only, signals, consts,
+    /// enums); hidden sections still count for the exit code
+    #[arg(long, value_enum, value_delimiter = ',', value_name = "SECTION,...")]
+    pub show: Vec<Section>,
+
+    /// Accepted findings: written to FILE (JSON) if missing, otherwise subtracted from the results
+    /// so only new findings are reported and fail the run
+    #[arg(long, value_name = "FILE")]
+    pub baseline: Option<PathBuf>,
+
+    /// Rewrite the `--baseline` file without entries whose definition no longer exists (deleted,
+    /// renamed or edited); categories not analyzed in this run are kept
+    #[arg(long, requires = "baseline")]
+    pub baseline_prune: bool,
+
+    /// Print each resolved option and where it came from (cli, config-file or default), then exit
+    #[arg(long)]
+    pub explain_config: bool,
+
+    /// Read options from FILE (default: gdcf.toml at the scan root, if present); flags override it
+    #[arg(long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
 }
 
 /// Format path for user-facing output: strip Windows extended path prefix `\\?\` so it displays as a normal path.
@@ -61,51 +458,559 @@ pub fn display_path(path: &Path) -> std::borrow::Cow<'_, str> {
     s
 }
 
-fn resolve_root(path: Option<&PathBuf>) -> Result<PathBuf, i32> {
+fn resolve_root(path: Option<&PathBuf>) -> Result<PathBuf, RunError> {
     let path = path.cloned().unwrap_or_else(|| PathBuf::from("."));
     let root = path.canonicalize().unwrap_or(path);
     if !root.is_dir() {
-        eprintln!("Error: not a directory: {}", display_path(&root));
-        return Err(2);
+        return Err(RunError::NotADirectory(root));
     }
     Ok(root)
 }
 
+/// Load `--config FILE`, or `gdcf.toml` at the root if it exists. A missing default file is not
+/// an error; a missing explicit file or an invalid one is.
+fn load_config(args: &Args, root: &Path) -> Result<Option<(PathBuf, Config)>, String> {
+    let path = match &args.config {
+        Some(path) => path.clone(),
+        None => {
+            let path = root.join(DEFAULT_CONFIG_FILE);
+            if !path.is_file() {
+                return Ok(None);
+            }
+            path
+        }
+    };
+    Config::from_file(&path).map(|config| Some((path, config)))
+}
+
+/// Fill options not given on the command line from the config file (explicit flags win).
+fn apply_config(args: &mut Args, config: Config) {
+    if args.test_dirs.is_empty() {
+        args.test_dirs = config.test_dirs;
+    }
+    // Explicit excludes from the file apply even with --no-default-excludes, which only drops
+    // the built-in defaults.
+    if args.exclude_dirs.is_empty() {
+        match config.exclude_dirs {
+            Some(dirs) if dirs.is_empty() => args.no_default_excludes = true,
+            Some(dirs) => args.exclude_dirs = dirs,
+            None => {}
+        }
+    }
+    // `--no-<flag>` (or `--default-excludes`) turns off a flag the file switched on.
+    args.no_default_excludes =
+        !args.default_excludes && (args.no_default_excludes || config.no_default_excludes);
+    if args.native_callbacks.is_empty() {
+        args.native_callbacks = config.native_callbacks;
+    }
+    if args.virtual_methods.is_empty() {
+        args.virtual_methods = config.virtual_methods;
+    }
+    args.quiet = !args.no_quiet && (args.quiet || config.quiet);
+    args.scoped = !args.no_scoped && (args.scoped || config.scoped);
+    args.autoloads = !args.no_autoloads && (args.autoloads || config.autoloads);
+    args.signals = !args.no_signals && (args.signals || config.signals);
+    args.consts = !args.no_consts && (args.consts || config.consts);
+    args.enums = !args.no_enums && (args.enums || config.enums);
+    args.format = args.format.or(config.format);
+}
+
+/// Everything one run reports, before output formatting.
+#[derive(Debug, Default)]
+struct Findings {
+    unused: Vec<FunctionDef>,
+    only_in_tests: Vec<FunctionDef>,
+    unused_signals: Vec<SignalDef>,
+    unused_consts: Vec<ConstDef>,
+    unused_enum_members: Vec<EnumMemberDef>,
+}
+
+impl Findings {
+    /// All sections as a report, with paths relative to `base` and severities from `args` (see
+    /// [`finding_severity`]).
+    fn to_report(&self, base: &Path, args: &Args) -> Report {
+        let mut report = Report::new(base, &self.unused, &self.only_in_tests);
+        report.add_unused_signals(base, &self.unused_signals);
+        report.add_unused_consts(base, &self.unused_consts);
+        report.add_unused_enum_members(base, &self.unused_enum_members);
+        report.set_severities(|category| finding_severity(args, category));
+        report
+    }
+
+    fn is_empty(&self) -> bool {
+        !self.has_unused() && self.only_in_tests.is_empty()
+    }
+
+    /// Any never-referenced definition (function, signal, constant or enum member).
+    fn has_unused(&self) -> bool {
+        !self.unused.is_empty()
+            || !self.unused_signals.is_empty()
+            || !self.unused_consts.is_empty()
+            || !self.unused_enum_members.is_empty()
+    }
+
+    /// Keep only findings in files for which `keep` is true.
+    fn retain_files(&mut self, keep: impl Fn(&Path) -> bool) {
+        self.unused.retain(|fd| keep(&fd.file));
+        self.only_in_tests.retain(|fd| keep(&fd.file));
+        self.unused_signals.retain(|sd| keep(&sd.file));
+        self.unused_consts.retain(|cd| keep(&cd.file));
+        self.unused_enum_members.retain(|em| keep(&em.file));
+    }
+
+    /// Drop the findings of sections not listed in `show` (all are kept when it is empty).
+    fn retain_sections(&mut self, show: &[Section]) {
+        let hidden = |section| !show.is_empty() && !show.contains(&section);
+        if hidden(Section::Functions) {
+            self.unused.clear();
+        }
+        if hidden(Section::TestOnly) {
+            self.only_in_tests.clear();
+        }
+        if hidden(Section::Signals) {
+            self.unused_signals.clear();
+        }
+        if hidden(Section::Consts) {
+            self.unused_consts.clear();
+        }
+        if hidden(Section::Enums) {
+            self.unused_enum_members.clear();
+        }
+    }
+}
+
+/// Severity of findings of `category`: the last `--severity` rule naming the category, else
+/// [`Category::default_severity`]. `--fail-on` only sets the exit code, not the severity.
+fn finding_severity(args: &Args, category: Category) -> Severity {
+    args.severity
+        .iter()
+        .rev()
+        .find(|rule| rule.categories.contains(&category))
+        .map_or(category.default_severity(), |rule| rule.severity)
+}
+
+/// Create the baseline at `path` from the current findings if it does not exist, then drop every
+/// finding it lists.
+fn apply_baseline(path: &Path, root: &Path, findings: &mut Findings) -> Result<(), String> {
+    let mut builder = EntryBuilder::new(root);
+    let fn_entry = |b: &mut EntryBuilder, category, fd: &FunctionDef| {
+        b.entry(category, &fd.name, &fd.file, fd.line)
+    };
+    let signal_entry = |b: &mut EntryBuilder, sd: &SignalDef| {
+        b.entry(Category::UnusedSignal, &sd.name, &sd.file, sd.line)
+    };
+    let const_entry = |b: &mut EntryBuilder, cd: &ConstDef| {
+        b.entry(Category::UnusedConst, &cd.name, &cd.file, cd.line)
+    };
+    let enum_entry = |b: &mut EntryBuilder, em: &EnumMemberDef| {
+        let name = em.qualified_name();
+        b.entry(Category::UnusedEnumMember, &name, &em.file, em.line)
+    };
+    if !path.exists() {
+        let mut entries = Vec::new();
+        for fd in &findings.unused {
+            entries.push(fn_entry(&mut builder, Category::Unused, fd));
+        }
+        for fd in &findings.only_in_tests {
+            entries.push(fn_entry(&mut builder, Category::TestOnly, fd));
+        }
+        for sd in &findings.unused_signals {
+            entries.push(signal_entry(&mut builder, sd));
+        }
+        for cd in &findings.unused_consts {
+            entries.push(const_entry(&mut builder, cd));
+        }
+        for em in &findings.unused_enum_members {
+            entries.push(enum_entry(&mut builder, em));
+        }
+        let count = entries.len();
+        Baseline::new(entries)
+            .save(path)
+            .map_err(|e| format!("cannot write baseline {}: {}", path.display(), e))?;
+        eprintln!(
+            "Wrote baseline with {} finding(s) to {}",
+            count,
+            display_path(path)
+        );
+    }
+    let baseline = Baseline::load(path)?;
+    let known = baseline.entry_set();
+    findings
+        .unused
+        .retain(|fd| !known.contains(&fn_entry(&mut builder, Category::Unused, fd)));
+    findings
+        .only_in_tests
+        .retain(|fd| !known.contains(&fn_entry(&mut builder, Category::TestOnly, fd)));
+    findings
+        .unused_signals
+        .retain(|sd| !known.contains(&signal_entry(&mut builder, sd)));
+    findings
+        .unused_consts
+        .retain(|cd| !known.contains(&const_entry(&mut builder, cd)));
+    findings
+        .unused_enum_members
+        .retain(|em| !known.contains(&enum_entry(&mut builder, em)));
+    Ok(())
+}
+
+/// `--baseline-prune`: drop baseline entries that match no current definition (same rule, name,
+/// file and definition line) and rewrite the file. Entries of categories this run did not
+/// analyze (e.g. constants without `--consts`) are kept. Returns the number removed.
+fn prune_baseline(
+    path: &Path,
+    root: &Path,
+    scan: &ScanResult,
+    args: &Args,
+) -> Result<usize, String> {
+    let mut baseline = Baseline::load(path)?;
+    let mut builder = EntryBuilder::new(root);
+    let mut live = HashSet::new();
+    for fd in &scan.definitions {
+        for category in [Category::Unused, Category::TestOnly] {
+            live.insert(builder.entry(category, &fd.name, &fd.file, fd.line));
+        }
+    }
+    if args.signals {
+        for sd in &scan.signals {
+            live.insert(builder.entry(Category::UnusedSignal, &sd.name, &sd.file, sd.line));
+        }
+    }
+    if args.consts {
+        for cd in &scan.consts {
+            live.insert(builder.entry(Category::UnusedConst, &cd.name, &cd.file, cd.line));
+        }
+    }
+    if args.enums {
+        for em in &scan.enum_members {
+            let name = em.qualified_name();
+            live.insert(builder.entry(Category::UnusedEnumMember, &name, &em.file, em.line));
+        }
+    }
+    let analyzed = |rule: &str| match rule {
+        "unused-signal" => args.signals,
+        "unused-const" => args.consts,
+        "unused-enum-member" => args.enums,
+        _ => true,
+    };
+    let removed = baseline.retain(|entry| !analyzed(&entry.rule) || live.contains(entry));
+    if removed > 0 {
+        baseline
+            .save(path)
+            .map_err(|e| format!("cannot write baseline {}: {}", path.display(), e))?;
+    }
+    Ok(removed)
+}
+
+/// Resolved settings as (name, value) pairs, for `--explain-config`.
+fn option_values(args: &Args) -> Vec<(&'static str, String)> {
+    let path_or_none = |p: &Option<PathBuf>| {
+        p.as_deref()
+            .map_or_else(|| "none".to_string(), |p| display_path(p).into_owned())
+    };
+    vec![
+        ("test_dirs", format!("{:?}", args.test_dirs)),
+        ("exclude_dirs", format!("{:?}", exclude_dirs(args))),
+        ("exclude_unused_in", format!("{:?}", args.exclude_unused_in)),
+        ("follow_symlinks", args.follow_symlinks.to_string()),
+        ("include_hidden", args.include_hidden.to_string()),
+        ("changed_paths", format!("{:?}", args.changed_paths)),
+        ("stdin_files", args.stdin_files.to_string()),
+        ("extensions", format!("{:?}", args.extensions)),
+        ("doc_refs", format!("{:?}", args.doc_refs)),
+        ("native_callbacks", format!("{:?}", args.native_callbacks)),
+        ("virtual_methods", format!("{:?}", args.virtual_methods)),
+        ("quiet", args.quiet.to_string()),
+        ("quiet_if_clean", args.quiet_if_clean.to_string()),
+        ("group_by_file", args.group_by_file.to_string()),
+        ("scoped", args.scoped.to_string()),
+        ("reachable", args.reachable.to_string()),
+        ("signals", args.signals.to_string()),
+        ("consts", args.consts.to_string()),
+        ("enums", args.enums.to_string()),
+        (
+            "skip_test_definitions",
+            args.skip_test_definitions.to_string(),
+        ),
+        ("no_tres", args.no_tres.to_string()),
+        (
+            "no_ignored_file_refs",
+            args.no_ignored_file_refs.to_string(),
+        ),
+        ("project", path_or_none(&args.project)),
+        ("autoloads", args.autoloads.to_string()),
+        ("warn_dynamic", args.warn_dynamic.to_string()),
+        (
+            "noref_pattern",
+            args.noref_pattern
+                .clone()
+                .unwrap_or_else(|| DEFAULT_NOREF_PATTERN.to_string()),
+        ),
+        (
+            "tscn_ref_properties",
+            format!("{:?}", args.tscn_ref_properties),
+        ),
+        (
+            "min_confidence",
+            args.min_confidence.map_or_else(
+                || "heuristic".to_string(),
+                |c| format!("{:?}", c).to_lowercase(),
+            ),
+        ),
+        (
+            "test_framework",
+            format!("{:?}", args.test_framework).to_lowercase(),
+        ),
+        ("strict_tests", args.strict_tests.to_string()),
+        ("sort", format!("{:?}", args.sort).to_lowercase()),
+        (
+            "format",
+            format!("{:?}", args.format.unwrap_or_default()).to_lowercase(),
+        ),
+        (
+            "findings_limit_per_name",
+            args.findings_limit_per_name
+                .map_or_else(|| "none".to_string(), |n| n.to_string()),
+        ),
+        ("with_signature", args.with_signature.to_string()),
+        ("cache_dir", path_or_none(&args.cache_dir)),
+        ("no_cache", args.no_cache.to_string()),
+        ("clear_cache", args.clear_cache.to_string()),
+        ("strip_prefix", path_or_none(&args.strip_prefix)),
+        ("output", path_or_none(&args.output)),
+        ("output_per_file", path_or_none(&args.output_per_file)),
+        ("baseline", path_or_none(&args.baseline)),
+        ("baseline_prune", args.baseline_prune.to_string()),
+        (
+            "fail_on",
+            args.fail_on
+                .to_possible_value()
+                .map_or_else(String::new, |v| v.get_name().to_string()),
+        ),
+        (
+            "severity",
+            format!(
+                "{:?}",
+                args.severity
+                    .iter()
+                    .map(|rule| {
+                        let kinds: Vec<_> = rule.categories.iter().map(|c| c.rule_id()).collect();
+                        format!("{}={}", kinds.join("+"), rule.severity.as_str())
+                    })
+                    .collect::<Vec<_>>()
+            ),
+        ),
+        (
+            "show",
+            format!(
+                "{:?}",
+                args.show
+                    .iter()
+                    .filter_map(|s| s.to_possible_value())
+                    .map(|v| v.get_name().to_string())
+                    .collect::<Vec<_>>()
+            ),
+        ),
+    ]
+}
+
+/// Print every resolved option with where its value came from: `cli` (differs from the default
+/// before the config file is applied), `config-file` (changed by the config file) or `default`.
+fn print_explain_config(
+    out: &mut dyn Write,
+    config_path: Option<&Path>,
+    from_cli: &[(&'static str, String)],
+    resolved: &[(&'static str, String)],
+) -> std::io::Result<()> {
+    let defaults = option_values(&Args::parse_from(["godot-dead-code"]));
+    let config = config_path.map_or_else(|| "none".into(), display_path);
+    writeln!(out, "Config file: {}", config)?;
+    for ((name, value), ((_, cli), (_, default))) in
+        resolved.iter().zip(from_cli.iter().zip(defaults.iter()))
+    {
+        let source = if cli != default {
+            "cli"
+        } else if value != cli {
+            "config-file"
+        } else {
+            "default"
+        };
+        writeln!(out, "  {} = {} ({})", name, value, source)?;
+    }
+    Ok(())
+}
+
+/// Record `<prefix><action>` as referenced from the project file for each declared input action.
+fn add_input_action_references(project_file: &Path, prefix: &str, scan: &mut ScanResult) {
+    let Some(project) = ProjectConfig::from_file(project_file) else {
+        return;
+    };
+    for (action, line) in project.input_actions {
+        let name = format!("{}{}", prefix, action);
+        scan.add_reference(name, project_file.to_path_buf(), line);
+    }
+}
+
+/// Contents of the ```gdscript / ```gd fenced blocks in `markdown`, with every other line blanked
+/// so line numbers stay those of the Markdown file.
+fn gdscript_fences(markdown: &str) -> String {
+    let mut in_fence = false;
+    let mut lines = Vec::new();
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if let Some(info) = trimmed.strip_prefix("```") {
+            let lang = info.split_whitespace().next().unwrap_or("");
+            in_fence = !in_fence && (lang == "gdscript" || lang == "gd");
+            lines.push("");
+        } else {
+            lines.push(if in_fence { line } else { "" });
+        }
+    }
+    lines.join("\n")
+}
+
+/// `--doc-refs`: calls in GDScript code fences of matching Markdown files count as references.
+fn add_doc_references(
+    root: &Path,
+    globs: &[String],
+    exclude_dirs: &[String],
+    ignore: &IgnorePatterns,
+    walk: WalkOptions,
+    scan: &mut ScanResult,
+) {
+    let docs = IgnorePatterns::new(globs);
+    let md_files =
+        iter_files_by_extension(root, &mut None, Some(exclude_dirs), ignore, ".md", walk);
+    for path in md_files {
+        if !docs.matches_file(&path, root) {
+            continue;
+        }
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let path = path.canonicalize().unwrap_or(path);
+        for (name, line) in find_function_references(&path, &gdscript_fences(&text)) {
+            scan.add_reference(name, path.clone(), line);
+        }
+    }
+}
+
+/// Cache directory used when `--cache-dir` is not given, relative to the root.
+const CACHE_DIR: &str = ".gdcf-cache";
+
+/// Where to cache scan results: `--cache-dir`, else `.gdcf-cache` under the root; none with
+/// `--no-cache`.
+fn cache_dir(args: &Args, root: &Path) -> Option<PathBuf> {
+    if args.no_cache {
+        return None;
+    }
+    Some(
+        args.cache_dir
+            .clone()
+            .unwrap_or_else(|| root.join(CACHE_DIR)),
+    )
+}
+
+/// Directory walk options from `--follow-symlinks` and `--include-hidden`.
+fn walk_options(args: &Args) -> WalkOptions {
+    WalkOptions {
+        follow_symlinks: args.follow_symlinks,
+        include_hidden: args.include_hidden,
+    }
+}
+
+/// The project.godot to read: `--project`, else the one at the scan root.
+fn project_file(args: &Args, root: &Path) -> PathBuf {
+    args.project
+        .clone()
+        .unwrap_or_else(|| root.join(PROJECT_FILE))
+}
+
+/// Directories skipped when no excludes are given and `--no-default-excludes` is not set.
+const DEFAULT_EXCLUDE_DIRS: [&str; 3] = ["**/addons", "**/.godot", "**/.import"];
+
+/// Directories to skip: user excludes (`--exclude-dir`, else config `exclude_dirs`) replace the
+/// defaults; `--no-default-excludes` drops the defaults only. `.gdcfignore` globs apply on top.
 fn exclude_dirs(args: &Args) -> Vec<String> {
     if !args.exclude_dirs.is_empty() {
         args.exclude_dirs.clone()
     } else if args.no_default_excludes {
         vec![]
     } else {
-        vec!["**/addons".to_string()]
+        DEFAULT_EXCLUDE_DIRS.iter().map(|d| d.to_string()).collect()
     }
 }
 
-fn build_is_test_path(root: &Path, test_dirs: &[String]) -> Box<dyn Fn(&Path) -> bool + Send> {
+/// Contents of `.gdcfignore`: gitignore-style path globs to skip, then (after an `[allow]` line)
+/// function names that are never reported. Blank lines and `#` comments are ignored.
+#[derive(Debug, Default)]
+struct GdcfIgnore {
+    globs: Vec<String>,
+    allow: Vec<String>,
+}
+
+fn read_gdcfignore(root: &Path) -> GdcfIgnore {
+    let mut ignore = GdcfIgnore::default();
+    let Ok(text) = std::fs::read_to_string(root.join(GDCF_IGNORE_FILE)) else {
+        return ignore;
+    };
+    let mut in_allow = false;
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.eq_ignore_ascii_case("[allow]") {
+            in_allow = true;
+        } else if in_allow {
+            ignore.allow.push(line.to_string());
+        } else {
+            ignore.globs.push(line.to_string());
+        }
+    }
+    ignore
+}
+
+fn build_is_test_path(root: &Path, test_dirs: &[String]) -> SharedTestPathFn {
     if test_dirs.is_empty() {
         let root = root.to_path_buf();
-        Box::new(move |path: &Path| default_is_test_path(&root, path))
+        Arc::new(move |path: &Path| default_is_test_path(&root, path))
     } else {
-        let root = root.to_path_buf();
-        let test_dirs = test_dirs.to_vec();
-        Box::new(move |path: &Path| {
-            let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
-            for d in &test_dirs {
-                let test_base = root.join(d).canonicalize().unwrap_or_else(|_| root.join(d));
-                if path.starts_with(&test_base) {
-                    return true;
-                }
-            }
-            false
+        // Scanned paths are canonical; resolve the test dirs once the same way.
+        let (globs, dirs): (Vec<&String>, Vec<&String>) =
+            test_dirs.iter().partition(|d| is_glob_pattern(d));
+        let test_bases: Vec<PathBuf> = dirs
+            .iter()
+            .map(|d| root.join(d).canonicalize().unwrap_or_else(|_| root.join(d)))
+            .collect();
+        let test_globs = IgnorePatterns::new(&globs);
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        Arc::new(move |path: &Path| {
+            test_bases.iter().any(|base| path.starts_with(base))
+                || (!test_globs.is_empty() && test_globs.matches_file(path, &root))
         })
     }
 }
 
-fn print_verbose_file_list(root: &Path, exclude_dirs: &[String], verbose: u8) {
+fn print_verbose_file_list(
+    root: &Path,
+    exclude_dirs: &[String],
+    ignore_globs: &[String],
+    extensions: &[String],
+    walk: WalkOptions,
+    verbose: u8,
+) {
     let mut debug_out: Option<&mut dyn std::io::Write> = None;
-    let mut gd_paths = iter_gd_files(root, &mut debug_out, Some(exclude_dirs));
+    let ignore = IgnorePatterns::new(ignore_globs);
+    let excludes = Some(exclude_dirs);
+    let mut gd_paths: Vec<PathBuf> = gdscript_suffixes(extensions)
+        .iter()
+        .flat_map(|suffix| {
+            iter_files_by_extension(root, &mut debug_out, excludes, &ignore, suffix, walk)
+        })
+        .collect();
     gd_paths.sort_by_key(|a| a.to_string_lossy().to_lowercase());
-    let mut tscn_paths = iter_tscn_files(root, &mut debug_out, Some(exclude_dirs));
+    gd_paths.dedup();
+    let mut tscn_paths =
+        iter_files_by_extension(root, &mut debug_out, excludes, &ignore, ".tscn", walk);
     tscn_paths.sort_by_key(|a| a.to_string_lossy().to_lowercase());
     eprintln!("Scanning: {}", display_path(root));
     eprintln!("  Root (resolved): {}", display_path(root));
@@ -165,7 +1070,7 @@ fn print_verbose_summary(root: &Path, scan: &ScanResult, verbose: u8) {
     eprintln!("  Total references: {}", total_refs);
 }
 
-fn run_debug_mode(root: &Path, func_name: &str, scan: &ScanResult) -> i32 {
+fn run_debug_mode(root: &Path, func_name: &str, scan: &ScanResult) {
     eprintln!("Debug: searching for references to '{}'", func_name);
     let defs: Vec<_> = scan
         .definitions
@@ -176,7 +1081,16 @@ fn run_debug_mode(root: &Path, func_name: &str, scan: &ScanResult) -> i32 {
     let refs = scan.references.get(func_name).cloned().unwrap_or_default();
     eprintln!("  Definitions found: {}", defs.len());
     for fd in &defs {
-        eprintln!("    {}:{}: {}", display_path(&fd.file), fd.line, fd.name);
+        match &fd.inner_class {
+            Some(class) => eprintln!(
+                "    {}:{}: {} (in class {})",
+                display_path(&fd.file),
+                fd.line,
+                fd.name,
+                class
+            ),
+            None => eprintln!("    {}:{}: {}", display_path(&fd.file), fd.line, fd.name),
+        }
     }
     eprintln!("  References found: {}", refs.len());
     let mut ref_list: Vec<_> = refs.into_iter().collect();
@@ -191,73 +1105,966 @@ fn run_debug_mode(root: &Path, func_name: &str, scan: &ScanResult) -> i32 {
     if ref_list.is_empty() && !defs.is_empty() {
         eprintln!("  Warning: no references found for '{}'", func_name);
     }
-    0
 }
 
-fn print_results(unused: &[FunctionDef], only_in_tests: &[FunctionDef]) -> i32 {
-    if !unused.is_empty() {
-        println!("Unused (never called):");
-        for fd in unused {
-            println!("  {}:{}: {}", display_path(&fd.file), fd.line, fd.name);
+/// How long file events must pause before `--watch` rescans (an editor save fires several).
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Watch `root` recursively and call `on_change` once per debounced batch of changes to files
+/// for which `is_watched` holds (see [`rerun_on_changes`]). Returns when `on_change` returns
+/// false, or with the watcher's error.
+pub fn watch_changes(
+    root: &Path,
+    is_watched: &dyn Fn(&Path) -> bool,
+    debounce: Duration,
+    on_change: &mut dyn FnMut() -> bool,
+) -> notify::Result<()> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    watcher.watch(root, RecursiveMode::Recursive)?;
+    rerun_on_changes(&rx, is_watched, debounce, on_change)
+}
+
+/// Call `on_change` for each change event in `events` that touches a path for which
+/// `is_watched` holds, after draining the events that follow within `debounce`. Access events
+/// (including the rescan's own reads) are ignored. Returns when `on_change` returns false, when
+/// every sender is gone, or with the first error event.
+pub fn rerun_on_changes(
+    events: &mpsc::Receiver<notify::Result<notify::Event>>,
+    is_watched: &dyn Fn(&Path) -> bool,
+    debounce: Duration,
+    on_change: &mut dyn FnMut() -> bool,
+) -> notify::Result<()> {
+    let is_relevant = |event: &notify::Event| {
+        !matches!(event.kind, EventKind::Access(_))
+            && event.paths.iter().any(|path| is_watched(path))
+    };
+    while let Ok(event) = events.recv() {
+        if !is_relevant(&event?) {
+            continue;
+        }
+        while events.recv_timeout(debounce).is_ok() {}
+        if !on_change() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// `--watch`: run, then run again after each change to a scanned script, scene or resource
+/// (same extensions, excludes and ignore globs as the scan) until interrupted. A failing run is
+/// reported on stderr and does not stop watching. With `clear_screen` (`out` is a terminal),
+/// each run replaces the last on screen.
+fn run_watch(
+    mut args: Args,
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+    clear_screen: bool,
+) -> Result<Outcome, RunError> {
+    args.watch = false;
+    let root = resolve_root(args.path.as_ref())?;
+    // Each run reads the config itself; the filter needs its excludes and extensions too.
+    let mut filter_args = args.clone();
+    if let Some((_, config)) = load_config(&args, &root).map_err(RunError::Config)? {
+        apply_config(&mut filter_args, config);
+    }
+    let suffixes = gdscript_suffixes(&filter_args.extensions);
+    let exclude_dirs = exclude_dirs(&filter_args);
+    let ignore = IgnorePatterns::new(&read_gdcfignore(&root).globs);
+    let walk = walk_options(&filter_args);
+    let is_watched = |path: &Path| {
+        let name = path.to_string_lossy().to_lowercase();
+        suffixes
+            .iter()
+            .map(String::as_str)
+            .chain([".tscn", ".tres"])
+            .any(|suffix| name.ends_with(suffix))
+            && is_walked_path(&root, path, &exclude_dirs, &ignore, walk)
+    };
+    let mut rerun = || {
+        if clear_screen {
+            // Clear the screen and move the cursor home.
+            let _ = write!(out, "\x1b[2J\x1b[H");
+        }
+        run_with_io(args.clone(), input, out);
+        let _ = out.flush();
+        true
+    };
+    rerun();
+    watch_changes(&root, &is_watched, WATCH_DEBOUNCE, &mut rerun)
+        .map_err(|e| RunError::Watch(root.clone(), e))?;
+    Ok(Outcome::default())
+}
+
+/// Scan progress on stderr, rewritten in place; the line is cleared once every file is done.
+fn print_progress(done: usize, total: usize) {
+    let line = format!("Scanning {}/{} files", done, total);
+    if done < total {
+        eprint!("\r{}", line);
+    } else {
+        eprint!("\r{}\r", " ".repeat(line.len()));
+    }
+}
+
+/// Warn about files the scan could not read; `-v` lists them.
+fn print_skipped_files(root: &Path, scan: &ScanResult, verbose: u8) {
+    eprintln!(
+        "Warning: skipped {} unreadable file(s)",
+        scan.skipped_files.len()
+    );
+    if verbose >= 1 {
+        for path in &scan.skipped_files {
+            let rel = path.strip_prefix(root).unwrap_or(path);
+            eprintln!("  {}", display_path(rel));
+        }
+    }
+}
+
+/// `--warn-dynamic`: one stderr line per call of a Callable looked up by key.
+fn print_dynamic_warnings(root: &Path, scan: &ScanResult) {
+    for (path, line, text) in &scan.dynamic_calls {
+        let rel = path.strip_prefix(root).unwrap_or(path);
+        eprintln!(
+            "Warning: {}:{}: dynamic call `{}...)` is not resolved to any function",
+            display_path(rel),
+            line,
+            text
+        );
+    }
+}
+
+/// Entries to print with at most `limit` sites per function name. The last shown site of a
+/// truncated name carries the number of hidden sites.
+fn limit_per_name(defs: &[FunctionDef], limit: Option<NonZeroUsize>) -> Vec<(&FunctionDef, usize)> {
+    let Some(limit) = limit.map(NonZeroUsize::get) else {
+        return defs.iter().map(|fd| (fd, 0)).collect();
+    };
+    let mut totals: HashMap<&str, usize> = HashMap::new();
+    for fd in defs {
+        *totals.entry(fd.name.as_str()).or_default() += 1;
+    }
+    let mut shown: HashMap<&str, usize> = HashMap::new();
+    let mut entries = Vec::new();
+    for fd in defs {
+        let count = shown.entry(fd.name.as_str()).or_default();
+        if *count >= limit {
+            continue;
+        }
+        *count += 1;
+        let total = totals[fd.name.as_str()];
+        let hidden = if *count == limit { total - limit } else { 0 };
+        entries.push((fd, hidden));
+    }
+    entries
+}
+
+/// Reorder reported functions by `key`; ties keep file/line order.
+fn sort_functions(defs: &mut [FunctionDef], key: SortKey) {
+    defs.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+    match key {
+        SortKey::File => {}
+        SortKey::Line => defs.sort_by_key(|fd| fd.line),
+        SortKey::Name => defs.sort_by(|a, b| a.name.cmp(&b.name)),
+        SortKey::Size => defs.sort_by_key(|fd| std::cmp::Reverse(fd.line_count())),
+    }
+}
+
+/// Settings for the text output sections.
+struct TextOptions<'a> {
+    limit_per_name: Option<NonZeroUsize>,
+    strip_prefix: Option<&'a Path>,
+    with_signature: bool,
+    group_by_file: bool,
+    /// Print "No unused functions found." when both function sections are empty (off when
+    /// `--show` hides them).
+    functions_shown: bool,
+}
+
+/// Path as printed in findings: `strip_prefix` removed when the path starts with it.
+fn output_path<'a>(path: &'a Path, strip_prefix: Option<&Path>) -> std::borrow::Cow<'a, str> {
+    display_path(
+        strip_prefix
+            .and_then(|prefix| path.strip_prefix(prefix).ok())
+            .unwrap_or(path),
+    )
+}
+
+fn print_section(
+    out: &mut dyn Write,
+    title: &str,
+    defs: &[FunctionDef],
+    opts: &TextOptions,
+    sources: &mut SourceLines,
+) -> std::io::Result<()> {
+    if defs.is_empty() {
+        return Ok(());
+    }
+    writeln!(out, "{}", title)?;
+    let entries = limit_per_name(defs, opts.limit_per_name);
+    if opts.group_by_file {
+        return print_grouped_entries(out, entries, opts, sources);
+    }
+    for (fd, hidden) in entries {
+        let path = output_path(&fd.file, opts.strip_prefix);
+        write!(out, "  {}:{}: {}", path, fd.line, fd.name)?;
+        if hidden > 0 {
+            write!(out, " (+{} more)", hidden)?;
+        }
+        writeln!(out)?;
+        if opts.with_signature {
+            if let Some(signature) = sources.line(&fd.file, fd.line) {
+                writeln!(out, "      {}", signature)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `--group-by-file` layout of a section: a `path:` header per file (sorted), then its functions
+/// as `line: name` sorted by line.
+fn print_grouped_entries(
+    out: &mut dyn Write,
+    entries: Vec<(&FunctionDef, usize)>,
+    opts: &TextOptions,
+    sources: &mut SourceLines,
+) -> std::io::Result<()> {
+    let mut by_file: BTreeMap<&Path, Vec<(&FunctionDef, usize)>> = BTreeMap::new();
+    for (fd, hidden) in entries {
+        by_file
+            .entry(fd.file.as_path())
+            .or_default()
+            .push((fd, hidden));
+    }
+    for (file, mut entries) in by_file {
+        writeln!(out, "  {}:", output_path(file, opts.strip_prefix))?;
+        entries.sort_by_key(|(fd, _)| fd.line);
+        for (fd, hidden) in entries {
+            write!(out, "    {}: {}", fd.line, fd.name)?;
+            if hidden > 0 {
+                write!(out, " (+{} more)", hidden)?;
+            }
+            writeln!(out)?;
+            if opts.with_signature {
+                if let Some(signature) = sources.line(&fd.file, fd.line) {
+                    writeln!(out, "        {}", signature)?;
+                }
+            }
         }
     }
-    if !only_in_tests.is_empty() {
-        println!("Only called from test code (not from main app):");
-        for fd in only_in_tests {
-            println!("  {}:{}: {}", display_path(&fd.file), fd.line, fd.name);
+    Ok(())
+}
+
+/// Print each group of same-named definitions found in several files; returns the group count.
+fn print_duplicates(out: &mut dyn Write, root: &Path, scan: &ScanResult) -> std::io::Result<usize> {
+    let groups = find_duplicate_definitions(scan);
+    if groups.is_empty() {
+        writeln!(out, "No duplicate function definitions found.")?;
+        return Ok(0);
+    }
+    writeln!(out, "Duplicate definitions (same name in several files):")?;
+    for group in &groups {
+        writeln!(out, "  {}", group[0].name)?;
+        for fd in group {
+            let path = display_path(fd.file.strip_prefix(root).unwrap_or(&fd.file));
+            writeln!(out, "    {}:{}", path, fd.line)?;
         }
     }
-    if unused.is_empty() && only_in_tests.is_empty() {
-        println!("No unused functions found.");
+    Ok(groups.len())
+}
+
+fn run_explain_unused(
+    out: &mut dyn Write,
+    root: &Path,
+    func_name: &str,
+    scan: &ScanResult,
+) -> std::io::Result<()> {
+    let rel = |p: &Path| display_path(p.strip_prefix(root).unwrap_or(p)).into_owned();
+    writeln!(out, "Explain unused: '{}'", func_name)?;
+    let defs: Vec<_> = scan
+        .definitions
+        .iter()
+        .filter(|fd| fd.name == func_name)
+        .collect();
+    writeln!(out, "  Definitions: {}", defs.len())?;
+    for fd in &defs {
+        writeln!(out, "    {}:{}: {}", rel(&fd.file), fd.line, fd.name)?;
     }
-    if !unused.is_empty() || !only_in_tests.is_empty() {
-        1
+    let def_sites = scan.def_sites();
+    let mut refs = scan.refs_excluding_def_sites(func_name, &def_sites);
+    refs.sort_by(|a, b| (a.path.as_path(), a.line).cmp(&(b.path.as_path(), b.line)));
+    writeln!(
+        out,
+        "  References (excluding definition lines): {}",
+        refs.len()
+    )?;
+    for r in &refs {
+        match &r.target {
+            Some(target) => writeln!(
+                out,
+                "    {}:{} (autoload call, only counts for {})",
+                rel(&r.path),
+                r.line,
+                rel(target)
+            )?,
+            None => writeln!(out, "    {}:{}", rel(&r.path), r.line)?,
+        }
+    }
+    if refs.is_empty() {
+        writeln!(out, "  Verdict: '{}' has no references.", func_name)?;
     } else {
-        0
+        writeln!(out, "  Verdict: '{}' is referenced.", func_name)?;
+    }
+    let mut near: Vec<&String> = scan
+        .references
+        .keys()
+        .filter(|name| edit_distance(name, func_name) == 1)
+        .collect();
+    near.sort();
+    let mut near_sites = Vec::new();
+    for name in near {
+        let defined = scan.definitions.iter().any(|fd| &fd.name == name);
+        for r in scan.refs_excluding_def_sites(name, &def_sites) {
+            near_sites.push((name, r, defined));
+        }
+    }
+    near_sites.sort_by(|a, b| {
+        (a.0, a.1.path.as_path(), a.1.line).cmp(&(b.0, b.1.path.as_path(), b.1.line))
+    });
+    writeln!(
+        out,
+        "  Near-miss references (edit distance 1): {}",
+        near_sites.len()
+    )?;
+    for (name, r, defined) in &near_sites {
+        let note = if *defined { "" } else { " (no such function)" };
+        writeln!(out, "    {}:{}: {}{}", rel(&r.path), r.line, name, note)?;
+    }
+    Ok(())
+}
+
+/// `--explain`: the verdict for each definition of `func_name`, from the computed findings.
+/// `suppressed` gives why an unused definition is filtered out of the report, if it is.
+fn run_explain(
+    out: &mut dyn Write,
+    root: &Path,
+    func_name: &str,
+    scan: &ScanResult,
+    findings: &Findings,
+    options: &AnalysisOptions,
+    suppressed: &dyn Fn(&FunctionDef) -> Option<&'static str>,
+) -> std::io::Result<()> {
+    let rel = |p: &Path| display_path(p.strip_prefix(root).unwrap_or(p)).into_owned();
+    let is_reported = |list: &[FunctionDef], fd: &FunctionDef| {
+        list.iter().any(|f| f.file == fd.file && f.line == fd.line)
+    };
+    writeln!(out, "Explain: '{}'", func_name)?;
+    let defs: Vec<_> = scan
+        .definitions
+        .iter()
+        .filter(|fd| fd.name == func_name)
+        .collect();
+    if defs.is_empty() {
+        writeln!(out, "  No definition of '{}' found.", func_name)?;
+    }
+    let def_sites = scan.def_sites();
+    for fd in defs {
+        let site = format!("{}:{}", rel(&fd.file), fd.line);
+        let (verdict, refs) = if is_reported(&findings.unused, fd) {
+            // With --reachable, a referenced function is reported when no caller is reachable.
+            let refs = counted_references(scan, fd, options);
+            let verdict = if refs.is_empty() {
+                "reported unused: no references"
+            } else {
+                "reported unused: only referenced from unreachable code"
+            };
+            (verdict.to_string(), refs)
+        } else if is_reported(&findings.only_in_tests, fd) {
+            (
+                "reported test-only: only referenced from test code".to_string(),
+                scan.refs_for(fd, &def_sites),
+            )
+        } else if let Some(reason) = always_used_reason(fd, options) {
+            (format!("used: {}", reason), Vec::new())
+        } else {
+            let refs = counted_references(scan, fd, options);
+            match suppressed(fd) {
+                Some(reason) if refs.is_empty() => (format!("not reported: {}", reason), refs),
+                _ => ("used: referenced".to_string(), refs),
+            }
+        };
+        writeln!(out, "  {}: {}", site, verdict)?;
+        let mut refs = refs;
+        refs.sort_by(|a, b| (a.path.as_path(), a.line).cmp(&(b.path.as_path(), b.line)));
+        for r in refs {
+            writeln!(out, "    {}:{}", rel(&r.path), r.line)?;
+        }
     }
+    Ok(())
 }
 
-pub fn run(mut args: Args) -> i32 {
+fn print_results(
+    out: &mut dyn Write,
+    findings: &Findings,
+    opts: &TextOptions,
+) -> std::io::Result<()> {
+    // `--with-signature` reads each file once for the whole report.
+    let mut sources = SourceLines::default();
+    print_section(
+        out,
+        "Unused (never called):",
+        &findings.unused,
+        opts,
+        &mut sources,
+    )?;
+    print_section(
+        out,
+        "Only called from test code (not from main app):",
+        &findings.only_in_tests,
+        opts,
+        &mut sources,
+    )?;
+    if !findings.unused_signals.is_empty() {
+        writeln!(out, "Unused signals (never emitted or connected):")?;
+        for sd in &findings.unused_signals {
+            let path = output_path(&sd.file, opts.strip_prefix);
+            writeln!(out, "  {}:{}: {}", path, sd.line, sd.name)?;
+        }
+    }
+    if !findings.unused_consts.is_empty() {
+        writeln!(out, "Unused constants (never referenced):")?;
+        for cd in &findings.unused_consts {
+            let path = output_path(&cd.file, opts.strip_prefix);
+            writeln!(out, "  {}:{}: {}", path, cd.line, cd.name)?;
+        }
+    }
+    if !findings.unused_enum_members.is_empty() {
+        writeln!(out, "Unused enum members (never referenced):")?;
+        for em in &findings.unused_enum_members {
+            let path = output_path(&em.file, opts.strip_prefix);
+            writeln!(out, "  {}:{}: {}", path, em.line, em.qualified_name())?;
+        }
+    }
+    if opts.functions_shown && findings.unused.is_empty() && findings.only_in_tests.is_empty() {
+        writeln!(out, "No unused functions found.")?;
+    }
+    Ok(())
+}
+
+/// One-line tail of the text report (for CI logs): finding counts and scan totals.
+fn print_summary(
+    out: &mut dyn Write,
+    findings: &Findings,
+    scan: &ScanResult,
+) -> std::io::Result<()> {
+    let total_refs: usize = scan.references.values().map(|s| s.len()).sum();
+    let unused = findings.unused.len()
+        + findings.unused_signals.len()
+        + findings.unused_consts.len()
+        + findings.unused_enum_members.len();
+    let files: HashSet<&Path> = (findings.unused.iter().chain(&findings.only_in_tests))
+        .map(|f| f.file.as_path())
+        .chain(findings.unused_signals.iter().map(|s| s.file.as_path()))
+        .chain(findings.unused_consts.iter().map(|c| c.file.as_path()))
+        .chain(
+            findings
+                .unused_enum_members
+                .iter()
+                .map(|m| m.file.as_path()),
+        )
+        .collect();
+    writeln!(
+        out,
+        "Found {} unused, {} test-only in {} files (scanned {} scripts, {} defs, {} refs)",
+        unused,
+        findings.only_in_tests.len(),
+        files.len(),
+        scan.script_refs.len(),
+        scan.definitions.len(),
+        total_refs
+    )
+}
+
+/// `--stdin-files`: the GDScript paths (ending in one of `suffixes`) among the non-blank lines
+/// of `input`.
+fn read_stdin_files(input: &mut dyn BufRead, suffixes: &[String]) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for line in input.lines() {
+        let line = line?;
+        let line = line.trim();
+        let lower = line.to_lowercase();
+        if suffixes
+            .iter()
+            .any(|suffix| lower.ends_with(suffix.as_str()))
+        {
+            files.push(PathBuf::from(line));
+        }
+    }
+    Ok(files)
+}
+
+/// `--output-per-file DIR`: write `DIR/<file>.json` for every file in `report` (paths are
+/// root-relative), mirroring the source tree. Files outside the root (e.g. reached through a
+/// followed symlink) are skipped with a warning so nothing is written outside DIR.
+fn write_per_file_reports(dir: &Path, report: &Report) -> std::io::Result<()> {
+    for (file, file_report) in report.split_by_file() {
+        let inside_root = file
+            .components()
+            .all(|c| matches!(c, std::path::Component::Normal(_)));
+        if !inside_root {
+            eprintln!(
+                "Warning: no per-file report for {} (outside the scanned root)",
+                display_path(file)
+            );
+            continue;
+        }
+        let mut name = file.as_os_str().to_owned();
+        name.push(".json");
+        let target = dir.join(name);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&target, json::to_json(&file_report) + "\n")?;
+    }
+    Ok(())
+}
+
+/// `--output FILE`: create FILE (and its parent directories) for the report.
+fn create_output_file(path: &Path) -> std::io::Result<std::io::BufWriter<std::fs::File>> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    Ok(std::io::BufWriter::new(std::fs::File::create(path)?))
+}
+
+fn load_json_report(path: &Path) -> Result<Report, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("cannot read report {}: {}", path.display(), e))?;
+    json::from_json(&text).map_err(|e| format!("invalid report {}: {}", path.display(), e))
+}
+
+/// `--compare OLD NEW`: print the findings added in NEW and removed since OLD. The outcome
+/// carries the added findings and exit code 1 if there are any.
+fn run_compare(out: &mut dyn Write, old: &Path, new: &Path) -> Result<Outcome, RunError> {
+    let old = load_json_report(old).map_err(RunError::Report)?;
+    let new = load_json_report(new).map_err(RunError::Report)?;
+    let diff = new.diff(&old);
+    let print_group = |out: &mut dyn Write, title: &str, findings: &[Finding]| {
+        writeln!(out, "{} ({}):", title, findings.len())?;
+        for f in findings {
+            let message = f.category.message(&f.name);
+            writeln!(out, "  {}:{}: {}", display_path(&f.file), f.line, message)?;
+        }
+        Ok::<_, std::io::Error>(())
+    };
+    print_group(out, "Added", &diff.added)
+        .and_then(|_| print_group(out, "Removed", &diff.removed))
+        .map_err(RunError::Output)?;
+    Ok(Outcome {
+        exit_code: i32::from(!diff.added.is_empty()),
+        findings: diff.added,
+    })
+}
+
+fn print_sarif(out: &mut dyn Write, report: &Report) -> std::io::Result<()> {
+    let log = sarif::to_sarif(report);
+    serde_json::to_writer_pretty(&mut *out, &log)?;
+    writeln!(out)
+}
+
+/// Result of a successful run.
+#[derive(Debug, Default)]
+pub struct Outcome {
+    /// Suggested process exit code: 1 if the findings fail the run (see `--fail-on`), else 0.
+    pub exit_code: i32,
+    /// Reported findings with root-relative paths, after baseline, `--changed-path` and `--show`
+    /// filtering; empty in modes that only print (`--explain`, `--duplicates`, ...).
+    pub findings: Vec<Finding>,
+}
+
+/// Why a run failed; the CLI prints it as `Error: <message>` and exits with
+/// [`RunError::exit_code`].
+#[derive(Debug)]
+pub enum RunError {
+    /// The scan root is not a directory.
+    NotADirectory(PathBuf),
+    /// `--config` (or the root's gdcf.toml) is missing or invalid.
+    Config(String),
+    /// `--noref-pattern` is not a valid regex.
+    InvalidNorefPattern(regex::Error),
+    /// `--project` names a file that does not exist.
+    ProjectNotFound(PathBuf),
+    /// `--clear-cache` could not delete the cache in this directory.
+    ClearCache(PathBuf, std::io::Error),
+    /// The `--stdin-files` list could not be read.
+    Stdin(std::io::Error),
+    /// A `--compare` report could not be read.
+    Report(String),
+    /// The `--baseline` file could not be read, pruned or created.
+    Baseline(String),
+    /// An `--output` / `--output-per-file` path could not be written.
+    Write(PathBuf, std::io::Error),
+    /// Writing results failed.
+    Output(std::io::Error),
+    /// `--watch` could not watch the root.
+    Watch(PathBuf, notify::Error),
+}
+
+impl RunError {
+    /// Process exit code for a failed run.
+    pub fn exit_code(&self) -> i32 {
+        2
+    }
+}
+
+impl std::fmt::Display for RunError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunError::NotADirectory(path) => write!(f, "not a directory: {}", display_path(path)),
+            RunError::Config(e) | RunError::Report(e) | RunError::Baseline(e) => f.write_str(e),
+            RunError::InvalidNorefPattern(e) => write!(f, "invalid --noref-pattern: {}", e),
+            RunError::ProjectNotFound(path) => {
+                write!(f, "project file not found: {}", display_path(path))
+            }
+            RunError::ClearCache(dir, e) => {
+                write!(f, "cannot clear cache in {}: {}", display_path(dir), e)
+            }
+            RunError::Stdin(e) => write!(f, "cannot read file list from stdin: {}", e),
+            RunError::Write(path, e) => write!(f, "cannot write {}: {}", display_path(path), e),
+            RunError::Output(e) => write!(f, "failed to write output: {}", e),
+            RunError::Watch(root, e) => write!(f, "cannot watch {}: {}", display_path(root), e),
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
+
+/// Run the CLI, writing results to stdout and reading `--stdin-files` paths from stdin.
+pub fn run(args: Args) -> Result<Outcome, RunError> {
+    let stdout = std::io::stdout();
+    let is_terminal = stdout.is_terminal();
+    let (input, out) = (&mut std::io::stdin().lock(), &mut stdout.lock());
+    if args.watch && args.compare.is_none() {
+        return run_watch(args, input, out, is_terminal);
+    }
+    try_run_with_io(args, input, out)
+}
+
+/// Exit code for a run's result, printing a failure to stderr.
+pub fn exit_code(result: Result<Outcome, RunError>) -> i32 {
+    match result {
+        Ok(outcome) => outcome.exit_code,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            e.exit_code()
+        }
+    }
+}
+
+/// Run the CLI, writing results to `out` (diagnostics and errors go to stderr). Returns the exit
+/// code.
+pub fn run_with_output(args: Args, out: &mut dyn Write) -> i32 {
+    run_with_io(args, &mut std::io::stdin().lock(), out)
+}
+
+/// Like [`run_with_output`], reading `--stdin-files` paths from `input` instead of stdin.
+pub fn run_with_io(args: Args, input: &mut dyn BufRead, out: &mut dyn Write) -> i32 {
+    exit_code(try_run_with_io(args, input, out))
+}
+
+/// Like [`run`], reading `--stdin-files` paths from `input` and writing results to `out`.
+pub fn try_run_with_io(
+    mut args: Args,
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+) -> Result<Outcome, RunError> {
+    if let Some([old, new]) = args.compare.as_deref() {
+        return run_compare(out, old, new);
+    }
+    if args.watch {
+        return run_watch(args, input, out, false);
+    }
     args.test_dirs.append(&mut args.tests_dirs);
 
-    let root = match resolve_root(args.path.as_ref()) {
-        Ok(r) => r,
-        Err(code) => return code,
+    let root = resolve_root(args.path.as_ref())?;
+    let from_cli = option_values(&args);
+    let config_path = match load_config(&args, &root).map_err(RunError::Config)? {
+        Some((path, config)) => {
+            apply_config(&mut args, config);
+            Some(path)
+        }
+        None => None,
     };
+    if args.explain_config {
+        let resolved = option_values(&args);
+        print_explain_config(out, config_path.as_deref(), &from_cli, &resolved)
+            .map_err(RunError::Output)?;
+        return Ok(Outcome::default());
+    }
+    if let Some(pattern) = &args.noref_pattern {
+        regex::Regex::new(pattern).map_err(RunError::InvalidNorefPattern)?;
+    }
+    if let Some(project) = args.project.as_ref().filter(|p| !p.is_file()) {
+        return Err(RunError::ProjectNotFound(project.clone()));
+    }
     let exclude_dirs = exclude_dirs(&args);
     let is_test_path = build_is_test_path(&root, &args.test_dirs);
+    let gdcfignore = read_gdcfignore(&root);
 
     if args.verbose >= 2 {
-        print_verbose_file_list(&root, &exclude_dirs, args.verbose);
+        print_verbose_file_list(
+            &root,
+            &exclude_dirs,
+            &gdcfignore.globs,
+            &args.extensions,
+            walk_options(&args),
+            args.verbose,
+        );
     }
 
+    if args.clear_cache {
+        let dir = args
+            .cache_dir
+            .clone()
+            .unwrap_or_else(|| root.join(CACHE_DIR));
+        ScanCache::clear(&dir).map_err(|e| RunError::ClearCache(dir, e))?;
+    }
     let mut debug_out: Option<&mut dyn std::io::Write> = None;
     // Run a single scan for verbose, debug, and analysis (avoids scanning twice when quiet).
-    let scan = scan_directory(&root, &mut debug_out, Some(&exclude_dirs));
+    let scan_options = ScanOptions {
+        exclude_dirs: exclude_dirs.clone(),
+        ignore_globs: gdcfignore.globs.clone(),
+        no_tres: args.no_tres,
+        no_ignored_file_refs: args.no_ignored_file_refs,
+        cache_dir: cache_dir(&args, &root),
+        tscn_ref_properties: args.tscn_ref_properties.clone(),
+        consts: args.consts,
+        enums: args.enums,
+        noref_pattern: args.noref_pattern.clone(),
+        autoloads: args.autoloads,
+        project_file: Some(project_file(&args, &root)),
+        follow_symlinks: args.follow_symlinks,
+        include_hidden: args.include_hidden,
+        extensions: args.extensions.clone(),
+    };
+    // Only a terminal gets the `\r` counter; redirected stderr (CI logs) stays clean.
+    let show_progress = !args.quiet && std::io::stderr().is_terminal();
+    let mut progress = |done: usize, total: usize| {
+        if show_progress {
+            print_progress(done, total);
+        }
+    };
+    let mut scan =
+        scan_directory_with_progress(&root, &mut debug_out, &scan_options, &mut progress);
+    if let Some(ref prefix) = args.input_action_prefix {
+        add_input_action_references(&project_file(&args, &root), prefix, &mut scan);
+    }
+    if !args.doc_refs.is_empty() {
+        let ignore = IgnorePatterns::new(&gdcfignore.globs);
+        add_doc_references(
+            &root,
+            &args.doc_refs,
+            &exclude_dirs,
+            &ignore,
+            walk_options(&args),
+            &mut scan,
+        );
+    }
 
     if args.verbose >= 1 {
         print_verbose_summary(&root, &scan, args.verbose);
     }
+    if !scan.skipped_files.is_empty() && !args.quiet {
+        print_skipped_files(&root, &scan, args.verbose);
+    }
+    if args.warn_dynamic && !args.quiet {
+        print_dynamic_warnings(&root, &scan);
+    }
 
     if let Some(ref func_name) = args.debug_function {
-        return run_debug_mode(&root, func_name, &scan);
+        run_debug_mode(&root, func_name, &scan);
+        return Ok(Outcome::default());
+    }
+    if let Some(ref func_name) = args.explain_unused {
+        run_explain_unused(out, &root, func_name, &scan).map_err(RunError::Output)?;
+        return Ok(Outcome::default());
+    }
+    if args.duplicates {
+        let duplicates = print_duplicates(out, &root, &scan).map_err(RunError::Output)?;
+        return Ok(Outcome {
+            exit_code: i32::from(duplicates > 0),
+            findings: Vec::new(),
+        });
     }
 
-    let unused = find_unused_functions(&root, Some(&scan), Some(&exclude_dirs));
-    let only_in_tests = find_only_test_referenced_functions(
-        &root,
-        Some(is_test_path),
-        Some(&scan),
-        Some(&exclude_dirs),
-    );
+    let analysis_options = AnalysisOptions {
+        scoped: args.scoped,
+        native_callbacks: args.native_callbacks.clone(),
+        virtual_methods: args.virtual_methods.clone(),
+        skip_test_definitions: args.skip_test_definitions.then(|| is_test_path.clone()),
+        min_confidence: args.min_confidence,
+        test_framework: args.test_framework,
+        strict_tests: args.strict_tests.then(|| is_test_path.clone()),
+    };
+    let mut unused = if args.reachable {
+        find_unreachable_functions(&scan, &analysis_options)
+    } else {
+        find_unused_functions_with(&scan, &analysis_options)
+    };
+    unused.retain(|fd| !gdcfignore.allow.contains(&fd.name));
+    let exclude_unused_in = IgnorePatterns::new(&args.exclude_unused_in);
+    if !exclude_unused_in.is_empty() {
+        unused.retain(|fd| !exclude_unused_in.matches_file(&fd.file, &root));
+    }
+    let mut only_in_tests =
+        find_only_test_referenced_functions_with(&scan, &*is_test_path, &analysis_options);
+    // Native callbacks and virtual methods are called by the engine or a framework, so test-only
+    // references don't matter either.
+    only_in_tests.retain(|fd| {
+        !args.native_callbacks.contains(&fd.name)
+            && !args.virtual_methods.contains(&fd.name)
+            && !gdcfignore.allow.contains(&fd.name)
+    });
 
-    if args.quiet {
-        return if unused.is_empty() && only_in_tests.is_empty() {
-            0
+    let mut findings = Findings {
+        unused,
+        only_in_tests,
+        unused_signals: if args.signals {
+            find_unused_signals(&scan)
         } else {
-            1
+            Vec::new()
+        },
+        unused_consts: if args.consts {
+            find_unused_consts(&scan)
+        } else {
+            Vec::new()
+        },
+        unused_enum_members: if args.enums {
+            find_unused_enum_members(&scan)
+        } else {
+            Vec::new()
+        },
+    };
+    if let Some(ref func_name) = args.explain {
+        let suppressed = |fd: &FunctionDef| {
+            if gdcfignore.allow.contains(&fd.name) {
+                Some("listed in the .gdcfignore [allow] section")
+            } else if exclude_unused_in.matches_file(&fd.file, &root) {
+                Some("in a file excluded by --exclude-unused-in")
+            } else {
+                None
+            }
         };
+        run_explain(
+            out,
+            &root,
+            func_name,
+            &scan,
+            &findings,
+            &analysis_options,
+            &suppressed,
+        )
+        .map_err(RunError::Output)?;
+        return Ok(Outcome::default());
+    }
+    if !args.changed_paths.is_empty() || args.stdin_files {
+        let mut changed = args.changed_paths.clone();
+        if args.stdin_files {
+            let files = read_stdin_files(input, &gdscript_suffixes(&args.extensions))
+                .map_err(RunError::Stdin)?;
+            changed.extend(files);
+        }
+        // Scanned paths are canonical; resolve the changed paths the same way. A file path
+        // `starts_with` itself, so listed files and directories are matched alike.
+        let changed: Vec<PathBuf> = changed
+            .iter()
+            .map(|p| root.join(p).canonicalize().unwrap_or_else(|_| root.join(p)))
+            .collect();
+        findings.retain_files(|file| changed.iter().any(|dir| file.starts_with(dir)));
+    }
+    if let Some(ref path) = args.baseline {
+        if args.baseline_prune && path.exists() {
+            let removed = prune_baseline(path, &root, &scan, &args).map_err(RunError::Baseline)?;
+            eprintln!(
+                "Pruned {} stale baseline entr{} from {}",
+                removed,
+                if removed == 1 { "y" } else { "ies" },
+                display_path(path)
+            );
+        }
+        apply_baseline(path, &root, &mut findings).map_err(RunError::Baseline)?;
+    }
+
+    sort_functions(&mut findings.unused, args.sort);
+    sort_functions(&mut findings.only_in_tests, args.sort);
+
+    // Hidden sections still count, so the exit code is taken before `--show` applies.
+    let all = findings.to_report(&root, &args);
+    let fails = if args.severity.is_empty() {
+        all.findings.iter().any(|f| args.fail_on.fails(f.category))
+    } else {
+        all.has_errors()
+    };
+    let exit_code = i32::from(fails);
+    findings.retain_sections(&args.show);
+    let report = findings.to_report(&root, &args);
+    if let Some(dir) = &args.output_per_file {
+        write_per_file_reports(dir, &report).map_err(|e| RunError::Write(dir.clone(), e))?;
+    }
+    let outcome = Outcome {
+        exit_code,
+        findings: report.findings,
+    };
+    // Quiet modes silence stdout only: a stale `--output` file from an earlier run must not survive.
+    if args.output.is_none() && (args.quiet || (args.quiet_if_clean && findings.is_empty())) {
+        return Ok(outcome);
     }
 
-    print_results(&unused, &only_in_tests)
+    let strip_prefix = args
+        .strip_prefix
+        .as_ref()
+        .map(|p| p.canonicalize().unwrap_or_else(|_| p.clone()));
+    // Machine formats use root-relative paths; a prefix above the root widens them instead.
+    let report_base = strip_prefix
+        .as_deref()
+        .filter(|prefix| root.starts_with(prefix))
+        .unwrap_or(&root);
+    let mut output_file;
+    let out: &mut dyn Write = match &args.output {
+        Some(path) => match create_output_file(path) {
+            Ok(file) => {
+                output_file = file;
+                &mut output_file
+            }
+            Err(e) => return Err(RunError::Write(path.clone(), e)),
+        },
+        None => out,
+    };
+    let format = args.format.unwrap_or_default();
+    let written = match format {
+        OutputFormat::Text => {
+            let opts = TextOptions {
+                limit_per_name: args.findings_limit_per_name,
+                strip_prefix: strip_prefix.as_deref(),
+                with_signature: args.with_signature,
+                group_by_file: args.group_by_file,
+                functions_shown: args.show.is_empty()
+                    || args.show.contains(&Section::Functions)
+                    || args.show.contains(&Section::TestOnly),
+            };
+            print_results(out, &findings, &opts).and_then(|_| print_summary(out, &findings, &scan))
+        }
+        OutputFormat::Sarif | OutputFormat::Github | OutputFormat::Json | OutputFormat::Junit => {
+            let report = findings.to_report(report_base, &args);
+            match format {
+                OutputFormat::Sarif => print_sarif(out, &report),
+                OutputFormat::Json => writeln!(out, "{}", json::to_json(&report)),
+                OutputFormat::Junit => out.write_all(junit::to_junit(&report).as_bytes()),
+                _ => out.write_all(github::to_github_annotations(&report).as_bytes()),
+            }
+        }
+    };
+    written
+        .and_then(|_| out.flush())
+        .map_err(RunError::Output)?;
+    Ok(outcome)
 }
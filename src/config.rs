@@ -0,0 +1,61 @@
+//! Options file (`gdcf.toml`): the same settings as the command-line flags, for repeatable runs.
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::cli::OutputFormat;
+
+/// Config file looked up at the scan root when `--config` is not given.
+pub const DEFAULT_CONFIG_FILE: &str = "gdcf.toml";
+
+/// Settings read from a config file. Keys are named like the `Args` fields; every key is optional.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub test_dirs: Vec<String>,
+    /// `None` keeps the default `**/addons`, `**/.godot`, `**/.import` exclusion; an empty list excludes nothing.
+    pub exclude_dirs: Option<Vec<String>>,
+    pub no_default_excludes: bool,
+    pub quiet: bool,
+    pub scoped: bool,
+    pub autoloads: bool,
+    pub signals: bool,
+    pub consts: bool,
+    pub enums: bool,
+    pub native_callbacks: Vec<String>,
+    pub virtual_methods: Vec<String>,
+    pub format: Option<OutputFormat>,
+}
+
+impl Config {
+    /// Parse a config file; the error message names the file.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read config {}: {}", path.display(), e))?;
+        toml::from_str(&text).map_err(|e| format!("invalid config {}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_known_keys() {
+        let config: Config = toml::from_str(
+            "test_dirs = [\"spec\"]\nexclude_dirs = [\"vendor\"]\nquiet = true\nformat = \"sarif\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.test_dirs, vec!["spec"]);
+        assert_eq!(config.exclude_dirs, Some(vec!["vendor".to_string()]));
+        assert!(config.quiet);
+        assert_eq!(config.format, Some(OutputFormat::Sarif));
+    }
+
+    #[test]
+    fn empty_file_is_default_and_unknown_keys_fail() {
+        assert_eq!(toml::from_str::<Config>("").unwrap(), Config::default());
+        assert!(toml::from_str::<Config>("exclude = [\"x\"]").is_err());
+    }
+}
@@ -1,10 +1,23 @@
 //! Find unused functions in Godot GDScript codebases.
 
+pub mod baseline;
 pub mod cli;
+pub mod config;
+pub mod report;
 pub mod scanner;
 
-pub use cli::{display_path, run, Args};
+pub use cli::{
+    display_path, run, run_with_io, run_with_output, try_run_with_io, Args, Outcome, OutputFormat,
+    RunError, Section, SeverityRule, SortKey,
+};
+pub use config::Config;
+pub use report::{Category, Finding, Report, ReportDiff, Severity};
 pub use scanner::{
-    default_is_test_path, find_only_test_referenced_functions, find_unused_functions,
-    iter_gd_files, iter_tscn_files, scan_directory, FunctionDef, ScanResult,
+    analyze, analyze_scan, default_is_test_path, find_duplicate_definitions,
+    find_only_test_referenced_functions, find_only_test_referenced_functions_with,
+    find_unreachable_functions, find_unused_functions, find_unused_functions_with,
+    find_unused_signals, iter_gd_files, iter_tscn_files, scan_directory,
+    scan_directory_with_options, scan_directory_with_progress, Analysis, AnalysisOptions,
+    AnalyzeOptions, ConstDef, EnumMemberDef, FunctionDef, ScanOptions, ScanResult,
+    SharedTestPathFn, SignalDef, TestFramework,
 };
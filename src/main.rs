@@ -1,7 +1,7 @@
 //! CLI entrypoint for godot-dead-code-finder.
 
 use clap::{CommandFactory, Parser};
-use gdcf::cli::{run, Args};
+use gdcf::cli::{exit_code, run, Args};
 
 fn main() {
     if std::env::args().len() == 1 {
@@ -9,6 +9,5 @@ fn main() {
         let _ = cmd.print_help();
         std::process::exit(0);
     }
-    let exit_code = run(Args::parse());
-    std::process::exit(exit_code);
+    std::process::exit(exit_code(run(Args::parse())));
 }
@@ -0,0 +1,42 @@
+//! GitHub Actions workflow commands: one `::error`, `::warning` or `::notice` annotation per
+//! finding.
+
+use super::{uri_path, Report, Severity};
+
+/// Escape annotation message data (`%`, CR, LF).
+fn escape_data(s: &str) -> String {
+    s.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape an annotation property value (data escapes plus `:` and `,`).
+fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// Annotation command for a severity (GitHub calls notes `notice`).
+fn command(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "notice",
+    }
+}
+
+/// Render every finding as `::<level> file=<path>,line=<line>::<message>`, one per line.
+pub fn to_github_annotations(report: &Report) -> String {
+    report
+        .findings
+        .iter()
+        .map(|f| {
+            format!(
+                "::{} file={},line={}::{}\n",
+                command(f.severity),
+                escape_property(&uri_path(&f.file)),
+                f.line,
+                escape_data(&f.category.message(&f.name))
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,57 @@
+//! Plain JSON report (`--format json`): the findings as written, readable back for `--compare`.
+
+use serde::{Deserialize, Serialize};
+
+use super::Report;
+
+/// Version of the JSON report layout.
+const JSON_REPORT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct JsonReport {
+    version: u32,
+    #[serde(flatten)]
+    report: Report,
+}
+
+/// Render the report as pretty-printed JSON: `{"version": 1, "findings": [...]}`.
+pub fn to_json(report: &Report) -> String {
+    let doc = JsonReport {
+        version: JSON_REPORT_VERSION,
+        report: report.clone(),
+    };
+    serde_json::to_string_pretty(&doc).unwrap_or_default()
+}
+
+/// Parse a report written by [`to_json`].
+pub fn from_json(text: &str) -> Result<Report, String> {
+    let doc: JsonReport = serde_json::from_str(text).map_err(|e| e.to_string())?;
+    if doc.version != JSON_REPORT_VERSION {
+        return Err(format!("unsupported report version {}", doc.version));
+    }
+    Ok(doc.report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{Category, Finding, Severity};
+
+    #[test]
+    fn json_round_trip() {
+        let report = Report {
+            findings: vec![Finding {
+                category: Category::TestOnly,
+                severity: Severity::Warning,
+                name: "helper".into(),
+                file: "src/util.gd".into(),
+                line: 4,
+            }],
+        };
+        let text = to_json(&report);
+        assert!(text.contains("\"category\": \"test-only-function\""));
+        assert!(text.contains("\"severity\": \"warning\""));
+        assert_eq!(from_json(&text).unwrap(), report);
+        assert!(from_json("{\"version\": 9, \"findings\": []}").is_err());
+    }
+}
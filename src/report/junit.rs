@@ -0,0 +1,62 @@
+//! JUnit XML output (`--format junit`) for test-report pipelines: one `<testsuite>` for unused
+//! definitions and one for test-only functions, each finding a failed `<testcase>`.
+
+use super::{uri_path, Category, Finding, Report};
+
+/// Escape text for an XML attribute or element body.
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// One `<testsuite>` named `name` holding `findings`.
+fn suite(name: &str, findings: &[&Finding]) -> String {
+    let mut xml = format!(
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\">\n",
+        name,
+        findings.len(),
+        findings.len()
+    );
+    for f in findings {
+        let file = escape(&uri_path(&f.file));
+        let message = escape(&f.category.message(&f.name));
+        xml.push_str(&format!(
+            "    <testcase name=\"{}\" classname=\"{}\" file=\"{}\" line=\"{}\">\n",
+            escape(&f.name),
+            file,
+            file,
+            f.line
+        ));
+        xml.push_str(&format!(
+            "      <failure message=\"{}\" type=\"{}\">{}:{}: {}</failure>\n",
+            message,
+            f.category.rule_id(),
+            file,
+            f.line,
+            message
+        ));
+        xml.push_str("    </testcase>\n");
+    }
+    xml.push_str("  </testsuite>\n");
+    xml
+}
+
+/// Render the report as a JUnit `<testsuites>` document: test-only functions in the `test-only`
+/// suite, every other finding (functions, signals, constants, enum members) in `unused`.
+pub fn to_junit(report: &Report) -> String {
+    let (test_only, unused): (Vec<&Finding>, Vec<&Finding>) = report
+        .findings
+        .iter()
+        .partition(|f| f.category == Category::TestOnly);
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <testsuites name=\"godot-dead-code\" tests=\"{}\" failures=\"{}\">\n{}{}</testsuites>\n",
+        report.findings.len(),
+        report.findings.len(),
+        suite("unused", &unused),
+        suite("test-only", &test_only)
+    )
+}
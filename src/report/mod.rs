@@ -0,0 +1,314 @@
+//! Format-independent findings report used by the machine-readable output formats.
+
+pub mod github;
+pub mod json;
+pub mod junit;
+pub mod sarif;
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::scanner::{ConstDef, EnumMemberDef, FunctionDef, SignalDef};
+
+/// Kind of finding; each maps to a stable rule id in machine-readable output.
+/// Serialized as the rule id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Category {
+    /// Function is never referenced anywhere.
+    #[serde(rename = "unused-function")]
+    Unused,
+    /// Function is only referenced from test code.
+    #[serde(rename = "test-only-function")]
+    TestOnly,
+    /// Signal is never emitted or connected (reported with `--signals`).
+    #[serde(rename = "unused-signal")]
+    UnusedSignal,
+    /// Constant is never used (reported with `--consts`).
+    #[serde(rename = "unused-const")]
+    UnusedConst,
+    /// Enum member is never used (reported with `--enums`).
+    #[serde(rename = "unused-enum-member")]
+    UnusedEnumMember,
+}
+
+impl Category {
+    /// Every category, in report order.
+    pub const ALL: [Category; 5] = [
+        Category::Unused,
+        Category::TestOnly,
+        Category::UnusedSignal,
+        Category::UnusedConst,
+        Category::UnusedEnumMember,
+    ];
+
+    /// Stable rule identifier (e.g. SARIF `ruleId`).
+    pub fn rule_id(self) -> &'static str {
+        match self {
+            Category::Unused => "unused-function",
+            Category::TestOnly => "test-only-function",
+            Category::UnusedSignal => "unused-signal",
+            Category::UnusedConst => "unused-const",
+            Category::UnusedEnumMember => "unused-enum-member",
+        }
+    }
+
+    /// One-line description of the rule.
+    pub fn description(self) -> &'static str {
+        match self {
+            Category::Unused => "Function is never called",
+            Category::TestOnly => "Function is only called from test code",
+            Category::UnusedSignal => "Signal is never emitted or connected",
+            Category::UnusedConst => "Constant is never used",
+            Category::UnusedEnumMember => "Enum member is never used",
+        }
+    }
+
+    /// Severity of this category's findings for kinds a `--severity` list leaves out:
+    /// never-referenced definitions are errors, test-only functions warnings.
+    pub fn default_severity(self) -> Severity {
+        match self {
+            Category::TestOnly => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+
+    /// Message for a single finding of this category.
+    pub fn message(self, name: &str) -> String {
+        match self {
+            Category::Unused => format!("{} is never called", name),
+            Category::TestOnly => format!("{} is only called from test code", name),
+            Category::UnusedSignal => format!("signal {} is never emitted or connected", name),
+            Category::UnusedConst => format!("constant {} is never used", name),
+            Category::UnusedEnumMember => format!("enum member {} is never used", name),
+        }
+    }
+}
+
+/// How serious a finding is; named like SARIF result levels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    #[default]
+    Warning,
+    Note,
+}
+
+impl Severity {
+    /// Parse a level name: `error`, `warning` (or `warn`) or `note`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "error" => Some(Severity::Error),
+            "warning" | "warn" => Some(Severity::Warning),
+            "note" => Some(Severity::Note),
+            _ => None,
+        }
+    }
+
+    /// Level name, as in SARIF `level`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+        }
+    }
+}
+
+/// A single reported function; `file` is relative to the scan root.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Finding {
+    pub category: Category,
+    /// Reports written before severities existed read back as warnings.
+    #[serde(default)]
+    pub severity: Severity,
+    pub name: String,
+    pub file: PathBuf,
+    pub line: u32,
+}
+
+/// All findings of one run, unused first, then test-only, then unused signals, constants and
+/// enum members.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    pub findings: Vec<Finding>,
+}
+
+impl Report {
+    /// Build a report from analysis results; paths are made relative to `root` where possible.
+    pub fn new(root: &Path, unused: &[FunctionDef], only_in_tests: &[FunctionDef]) -> Self {
+        let to_finding = |category: Category, fd: &FunctionDef| Finding {
+            category,
+            severity: category.default_severity(),
+            name: fd.name.clone(),
+            file: fd.file.strip_prefix(root).unwrap_or(&fd.file).to_path_buf(),
+            line: fd.line,
+        };
+        let findings = unused
+            .iter()
+            .map(|fd| to_finding(Category::Unused, fd))
+            .chain(
+                only_in_tests
+                    .iter()
+                    .map(|fd| to_finding(Category::TestOnly, fd)),
+            )
+            .collect();
+        Report { findings }
+    }
+
+    /// Append unused-signal findings; paths are made relative to `root` where possible.
+    pub fn add_unused_signals(&mut self, root: &Path, signals: &[SignalDef]) {
+        self.findings.extend(signals.iter().map(|sd| Finding {
+            category: Category::UnusedSignal,
+            severity: Category::UnusedSignal.default_severity(),
+            name: sd.name.clone(),
+            file: sd.file.strip_prefix(root).unwrap_or(&sd.file).to_path_buf(),
+            line: sd.line,
+        }));
+    }
+
+    /// Append unused-constant findings; paths are made relative to `root` where possible.
+    pub fn add_unused_consts(&mut self, root: &Path, consts: &[ConstDef]) {
+        self.findings.extend(consts.iter().map(|cd| Finding {
+            category: Category::UnusedConst,
+            severity: Category::UnusedConst.default_severity(),
+            name: cd.name.clone(),
+            file: cd.file.strip_prefix(root).unwrap_or(&cd.file).to_path_buf(),
+            line: cd.line,
+        }));
+    }
+
+    /// Append unused-enum-member findings (named `Enum.MEMBER`); paths are made relative to
+    /// `root` where possible.
+    pub fn add_unused_enum_members(&mut self, root: &Path, members: &[EnumMemberDef]) {
+        self.findings.extend(members.iter().map(|em| Finding {
+            category: Category::UnusedEnumMember,
+            severity: Category::UnusedEnumMember.default_severity(),
+            name: em.qualified_name(),
+            file: em.file.strip_prefix(root).unwrap_or(&em.file).to_path_buf(),
+            line: em.line,
+        }));
+    }
+
+    /// Set the severity of every finding to `severity_of` its category.
+    pub fn set_severities(&mut self, severity_of: impl Fn(Category) -> Severity) {
+        for f in &mut self.findings {
+            f.severity = severity_of(f.category);
+        }
+    }
+
+    /// True if any finding has error severity.
+    pub fn has_errors(&self) -> bool {
+        self.findings.iter().any(|f| f.severity == Severity::Error)
+    }
+}
+
+/// Findings that appeared or disappeared relative to a baseline report. Findings are matched by
+/// `(name, file)`, so a function that only moved to another line is neither added nor removed.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReportDiff {
+    /// Findings in the current report with no match in the baseline.
+    pub added: Vec<Finding>,
+    /// Baseline findings with no match in the current report.
+    pub removed: Vec<Finding>,
+}
+
+impl ReportDiff {
+    /// True if nothing was added or removed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl Report {
+    /// Compare this report against `baseline`; see [`ReportDiff`].
+    pub fn diff(&self, baseline: &Report) -> ReportDiff {
+        let keys = |r: &Report| -> HashSet<(String, PathBuf)> {
+            r.findings
+                .iter()
+                .map(|f| (f.name.clone(), f.file.clone()))
+                .collect()
+        };
+        let (current, base) = (keys(self), keys(baseline));
+        let missing_from = |findings: &[Finding], other: &HashSet<(String, PathBuf)>| {
+            findings
+                .iter()
+                .filter(|f| !other.contains(&(f.name.clone(), f.file.clone())))
+                .cloned()
+                .collect()
+        };
+        ReportDiff {
+            added: missing_from(&self.findings, &base),
+            removed: missing_from(&baseline.findings, &current),
+        }
+    }
+
+    /// One report per file with findings, keyed by the finding's (root-relative) path.
+    pub fn split_by_file(&self) -> BTreeMap<&Path, Report> {
+        let mut by_file: BTreeMap<&Path, Report> = BTreeMap::new();
+        for f in &self.findings {
+            by_file
+                .entry(f.file.as_path())
+                .or_default()
+                .findings
+                .push(f.clone());
+        }
+        by_file
+    }
+}
+
+/// Path as a forward-slash URI fragment (portable across platforms).
+pub(crate) fn uri_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(name: &str, file: &str, line: u32) -> Finding {
+        Finding {
+            category: Category::Unused,
+            severity: Severity::Error,
+            name: name.to_string(),
+            file: PathBuf::from(file),
+            line,
+        }
+    }
+
+    #[test]
+    fn diff_reports_added_and_removed_by_name_and_file() {
+        let baseline = Report {
+            findings: vec![
+                finding("kept", "a.gd", 3),
+                finding("fixed", "a.gd", 8),
+                finding("moved_file", "b.gd", 1),
+            ],
+        };
+        let current = Report {
+            findings: vec![
+                finding("kept", "a.gd", 10),
+                finding("moved_file", "c.gd", 1),
+                finding("new_dead", "b.gd", 4),
+            ],
+        };
+        let diff = current.diff(&baseline);
+        assert_eq!(
+            diff.added,
+            vec![
+                finding("moved_file", "c.gd", 1),
+                finding("new_dead", "b.gd", 4)
+            ]
+        );
+        assert_eq!(
+            diff.removed,
+            vec![
+                finding("fixed", "a.gd", 8),
+                finding("moved_file", "b.gd", 1)
+            ]
+        );
+        assert!(current.diff(&current).is_empty());
+    }
+}
@@ -0,0 +1,56 @@
+//! SARIF 2.1.0 output (e.g. for GitHub code scanning).
+
+use serde_json::{json, Value};
+
+use super::{uri_path, Category, Report};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const TOOL_NAME: &str = "godot-dead-code";
+
+/// Build a SARIF 2.1.0 log with one run; each result's `level` is the finding's severity.
+pub fn to_sarif(report: &Report) -> Value {
+    let rules: Vec<Value> = Category::ALL
+        .iter()
+        .map(|c| {
+            json!({
+                "id": c.rule_id(),
+                "shortDescription": { "text": c.description() },
+            })
+        })
+        .collect();
+    let results: Vec<Value> = report
+        .findings
+        .iter()
+        .map(|f| {
+            json!({
+                "ruleId": f.category.rule_id(),
+                "level": f.severity.as_str(),
+                "message": { "text": f.category.message(&f.name) },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": {
+                            "uri": uri_path(&f.file),
+                            "uriBaseId": "%SRCROOT%",
+                        },
+                        "region": { "startLine": f.line },
+                    }
+                }],
+            })
+        })
+        .collect();
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": TOOL_NAME,
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    })
+}
@@ -0,0 +1,71 @@
+//! One-call analysis with structured results (for embedders such as editor integrations).
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::super::models::{FunctionDef, RefSite, ScanResult};
+use super::super::scan::{scan_directory_with_options, ScanOptions};
+use super::{
+    default_is_test_path, find_only_test_referenced_functions_with, find_unused_functions_with,
+    AnalysisOptions, SharedTestPathFn,
+};
+
+/// Options for [`analyze`]: how to scan, how to match, and what counts as test code.
+#[derive(Clone, Default)]
+pub struct AnalyzeOptions {
+    pub scan: ScanOptions,
+    pub analysis: AnalysisOptions,
+    /// Test-code predicate; `None` uses [`super::default_is_test_path`].
+    pub is_test_path: Option<SharedTestPathFn>,
+}
+
+impl std::fmt::Debug for AnalyzeOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnalyzeOptions")
+            .field("scan", &self.scan)
+            .field("analysis", &self.analysis)
+            .field("is_test_path", &self.is_test_path.is_some())
+            .finish()
+    }
+}
+
+/// Analysis results: findings plus who references each defined function.
+#[derive(Debug, Clone, Default)]
+pub struct Analysis {
+    pub unused: Vec<FunctionDef>,
+    pub test_only: Vec<FunctionDef>,
+    /// Function name -> reference sites (definition lines excluded), sorted by path and line.
+    pub reference_map: HashMap<String, Vec<RefSite>>,
+}
+
+/// Scan `root` and analyze it in one call.
+pub fn analyze(root: &Path, opts: &AnalyzeOptions) -> Analysis {
+    let mut debug_out = None;
+    let scan = scan_directory_with_options(root, &mut debug_out, &opts.scan);
+    analyze_scan(root, &scan, opts)
+}
+
+/// Like [`analyze`] over an existing scan (`opts.scan` is not used).
+pub fn analyze_scan(root: &Path, scan: &ScanResult, opts: &AnalyzeOptions) -> Analysis {
+    let unused = find_unused_functions_with(scan, &opts.analysis);
+    let is_test_path = |p: &Path| match &opts.is_test_path {
+        Some(f) => f(p),
+        None => default_is_test_path(root, p),
+    };
+    let test_only = find_only_test_referenced_functions_with(scan, &is_test_path, &opts.analysis);
+    let def_sites = scan.def_sites();
+    let mut reference_map = HashMap::new();
+    for fd in &scan.definitions {
+        if reference_map.contains_key(&fd.name) {
+            continue;
+        }
+        let mut refs = scan.refs_excluding_def_sites(&fd.name, &def_sites);
+        refs.sort_by(|a, b| (&a.path, a.line).cmp(&(&b.path, b.line)));
+        reference_map.insert(fd.name.clone(), refs);
+    }
+    Analysis {
+        unused,
+        test_only,
+        reference_map,
+    }
+}
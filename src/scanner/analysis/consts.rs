@@ -0,0 +1,19 @@
+//! Find `const` declarations that are never used.
+
+use super::super::models::{ConstDef, ScanResult};
+
+/// Return list of constant declarations whose name never appears as an identifier token outside
+/// its own declaration line (tokens are collected with `ScanOptions::consts`). Constants tagged
+/// with an ignore marker are never reported.
+pub fn find_unused_consts(scan: &ScanResult) -> Vec<ConstDef> {
+    scan.consts
+        .iter()
+        .filter(|cd| !cd.ignore_dead_code)
+        .filter(|cd| {
+            scan.token_references
+                .get(&cd.name)
+                .is_none_or(|sites| sites.iter().all(|s| s.path == cd.file && s.line == cd.line))
+        })
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,39 @@
+//! Find function names defined in more than one file.
+
+use std::collections::BTreeMap;
+
+use super::super::constants::{is_engine_callback, TestFramework};
+use super::super::models::{FunctionDef, ScanResult};
+
+/// Return groups of definitions sharing a name across two or more files (e.g. a helper pasted into
+/// two autoloads), sorted by name; each group lists its sites by (file, line). Name-based
+/// matching credits every copy with the same references, so one of them may be dead.
+/// Overrides are not copies: a definition is only listed if the group has another in a different
+/// file that neither extends nor is extended by its own file.
+/// Engine callbacks and test functions/hooks (GUT, GdUnit4) are expected in many scripts and are skipped.
+pub fn find_duplicate_definitions(scan: &ScanResult) -> Vec<Vec<FunctionDef>> {
+    let mut by_name: BTreeMap<&str, Vec<&FunctionDef>> = BTreeMap::new();
+    for fd in &scan.definitions {
+        if is_engine_callback(&fd.name) || TestFramework::Both.is_test_function(&fd.name) {
+            continue;
+        }
+        by_name.entry(fd.name.as_str()).or_default().push(fd);
+    }
+    by_name
+        .into_values()
+        .filter_map(|mut defs| {
+            defs.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+            let unrelated = |a: &FunctionDef, b: &FunctionDef| {
+                a.file != b.file
+                    && !scan.inherits_from(&a.file, &b.file)
+                    && !scan.inherits_from(&b.file, &a.file)
+            };
+            let copies: Vec<FunctionDef> = defs
+                .iter()
+                .filter(|a| defs.iter().any(|b| unrelated(a, b)))
+                .map(|fd| (*fd).clone())
+                .collect();
+            (!copies.is_empty()).then_some(copies)
+        })
+        .collect()
+}
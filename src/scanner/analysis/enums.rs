@@ -0,0 +1,19 @@
+//! Find enum members that are never used.
+
+use super::super::models::{EnumMemberDef, ScanResult};
+
+/// Return list of enum members whose name never appears as an identifier token outside its own
+/// declaration line: bare (`IDLE`) and qualified (`State.IDLE`) uses both count (tokens are
+/// collected with `ScanOptions::enums`). Members tagged with an ignore marker are never reported.
+pub fn find_unused_enum_members(scan: &ScanResult) -> Vec<EnumMemberDef> {
+    scan.enum_members
+        .iter()
+        .filter(|em| !em.ignore_dead_code)
+        .filter(|em| {
+            scan.token_references
+                .get(&em.name)
+                .is_none_or(|sites| sites.iter().all(|s| s.path == em.file && s.line == em.line))
+        })
+        .cloned()
+        .collect()
+}
@@ -1,14 +1,80 @@
-//! Analyze scan results: unused functions and test-only referenced functions.
+//! Analyze scan results: unused functions, test-only referenced functions and unused signals.
 
+use std::fmt;
 use std::path::Path;
+use std::sync::Arc;
 
+use super::constants::TestFramework;
+use super::models::Confidence;
+
+mod analyze;
+mod consts;
+mod duplicates;
+mod enums;
+mod reachability;
+mod signals;
 mod test_referenced;
 mod unused;
 
+pub use analyze::{analyze, analyze_scan, Analysis, AnalyzeOptions};
+pub use consts::find_unused_consts;
+pub use duplicates::find_duplicate_definitions;
+pub use enums::find_unused_enum_members;
+pub use reachability::find_unreachable_functions;
+pub use signals::find_unused_signals;
 #[allow(unused_imports)]
 // re-exported for public API (Option<IsTestPathFn> in find_only_test_referenced_functions)
-pub use test_referenced::{find_only_test_referenced_functions, IsTestPathFn};
-pub use unused::find_unused_functions;
+pub use test_referenced::{
+    find_only_test_referenced_functions, find_only_test_referenced_functions_with, IsTestPathFn,
+};
+pub use unused::{
+    always_used_reason, counted_references, find_unused_functions, find_unused_functions_with,
+};
+
+/// Shared test-code predicate (see [`AnalysisOptions::skip_test_definitions`]).
+pub type SharedTestPathFn = Arc<dyn Fn(&Path) -> bool + Send + Sync>;
+
+/// Options for the unused-function analysis.
+#[derive(Clone, Default)]
+pub struct AnalysisOptions {
+    /// Scope-aware matching: a bare reference (`helper()`, `= helper`, `f(helper)`) only counts
+    /// for a definition in the same file, an `extends` ancestor, or a script the caller names via
+    /// its `class_name` or a preload/load. Qualified and by-name references still count everywhere.
+    pub scoped: bool,
+    /// Functions invoked from native code (GDExtension/GDNative callbacks); always treated as used.
+    pub native_callbacks: Vec<String>,
+    /// Project-specific virtual methods invoked by a framework base class (e.g. `_on_spawn`);
+    /// always treated as used, in addition to the built-in engine callbacks.
+    pub virtual_methods: Vec<String>,
+    /// When set, definitions in files this predicate marks as test code are never reported as
+    /// unused (test-only references to production code are still analyzed separately).
+    pub skip_test_definitions: Option<SharedTestPathFn>,
+    /// When set, only references at least this certain count (e.g. `Exact` ignores identifiers
+    /// matched as call arguments or assigned values), for both the unused and test-only checks.
+    pub min_confidence: Option<Confidence>,
+    /// Test framework whose `test_*` methods and lifecycle hooks are always treated as used.
+    pub test_framework: TestFramework,
+    /// When set, `test_*` methods are only treated as used in files this predicate marks as test
+    /// code, so unused `test_*` helpers elsewhere are reported; lifecycle hooks still count anywhere.
+    pub strict_tests: Option<SharedTestPathFn>,
+}
+
+impl fmt::Debug for AnalysisOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnalysisOptions")
+            .field("scoped", &self.scoped)
+            .field("native_callbacks", &self.native_callbacks)
+            .field("virtual_methods", &self.virtual_methods)
+            .field(
+                "skip_test_definitions",
+                &self.skip_test_definitions.is_some(),
+            )
+            .field("min_confidence", &self.min_confidence)
+            .field("test_framework", &self.test_framework)
+            .field("strict_tests", &self.strict_tests.is_some())
+            .finish()
+    }
+}
 
 /// Return true if path is considered test code (under root).
 /// Default: any path segment is 'tests' or 'test' (case-insensitive), or filename stem is *_test / test_* (case-insensitive).
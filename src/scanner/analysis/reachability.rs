@@ -0,0 +1,70 @@
+//! Find function definitions not reachable from any entry point (dead clusters included).
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use super::super::models::{FunctionDef, ScanResult};
+use super::unused::{counted_refs, is_always_used};
+use super::AnalysisOptions;
+
+/// Index of the innermost definition whose body spans `line` in `file`, if any.
+fn enclosing_definition(
+    by_file: &HashMap<&Path, Vec<usize>>,
+    definitions: &[FunctionDef],
+    file: &Path,
+    line: u32,
+) -> Option<usize> {
+    by_file
+        .get(file)?
+        .iter()
+        .copied()
+        .filter(|&i| definitions[i].line <= line && line <= definitions[i].end_line)
+        .max_by_key(|&i| definitions[i].line)
+}
+
+/// Return definitions that no chain of calls connects to an entry point.
+///
+/// The call graph has an edge from the function enclosing each reference to every definition the
+/// reference counts for (under the same rules as [`super::find_unused_functions_with`]); a
+/// reference is only an edge, so functions calling each other but called from nowhere else are
+/// all reported. Entry points are the definitions never reported as unused (engine callbacks,
+/// `@rpc`, test functions, tagged functions, native callbacks and virtual methods) and every
+/// definition referenced from outside a function body: script-level code (variable initializers,
+/// also in autoloads), .tscn/.tres connections and project.godot.
+pub fn find_unreachable_functions(
+    scan: &ScanResult,
+    options: &AnalysisOptions,
+) -> Vec<FunctionDef> {
+    let definitions = &scan.definitions;
+    let mut by_file: HashMap<&Path, Vec<usize>> = HashMap::new();
+    for (i, fd) in definitions.iter().enumerate() {
+        by_file.entry(fd.file.as_path()).or_default().push(i);
+    }
+    let def_sites = scan.def_sites();
+    let mut callees: Vec<Vec<usize>> = vec![Vec::new(); definitions.len()];
+    let mut pending = Vec::new();
+    for (i, fd) in definitions.iter().enumerate() {
+        if is_always_used(fd, options) {
+            pending.push(i);
+            continue;
+        }
+        for r in counted_refs(scan, fd, &def_sites, options) {
+            match enclosing_definition(&by_file, definitions, &r.path, r.line) {
+                Some(caller) => callees[caller].push(i),
+                None => pending.push(i),
+            }
+        }
+    }
+    let mut reached = HashSet::new();
+    while let Some(i) = pending.pop() {
+        if reached.insert(i) {
+            pending.extend(callees[i].iter().copied());
+        }
+    }
+    definitions
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !reached.contains(i))
+        .map(|(_, fd)| fd.clone())
+        .collect()
+}
@@ -0,0 +1,19 @@
+//! Find signal declarations that are never emitted or connected.
+
+use super::super::models::{ScanResult, SignalDef};
+
+/// Return list of signal declarations with no reference anywhere: no `name.emit(`,
+/// `emit_signal("name")`, `name.connect(`, `connect("name", ...)` or .tscn `signal="name"`.
+/// Signals tagged with an ignore marker are never reported.
+pub fn find_unused_signals(scan: &ScanResult) -> Vec<SignalDef> {
+    scan.signals
+        .iter()
+        .filter(|sd| !sd.ignore_dead_code)
+        .filter(|sd| {
+            scan.signal_references
+                .get(&sd.name)
+                .is_none_or(|sites| sites.is_empty())
+        })
+        .cloned()
+        .collect()
+}
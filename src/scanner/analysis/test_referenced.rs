@@ -6,7 +6,8 @@ use super::super::constants::is_engine_callback;
 use super::super::models::{FunctionDef, ScanResult};
 use super::super::scan::scan_directory;
 
-use super::default_is_test_path;
+use super::unused::counted_refs;
+use super::{default_is_test_path, AnalysisOptions};
 
 /// Callback type: returns true if the path is considered test code.
 pub type IsTestPathFn = Box<dyn Fn(&Path) -> bool>;
@@ -34,10 +35,22 @@ pub fn find_only_test_referenced_functions(
             return find_only_test_referenced_functions(root, None, Some(&s), exclude_dirs);
         }
     };
+    find_only_test_referenced_functions_with(scan, &is_test_path, &AnalysisOptions::default())
+}
+
+/// Like [`find_only_test_referenced_functions`] over an existing scan, counting only the
+/// references [`super::find_unused_functions_with`] counts under `options` (scoped matching,
+/// confidence threshold), so no function is both unused and test-only.
+pub fn find_only_test_referenced_functions_with(
+    scan: &ScanResult,
+    is_test_path: &dyn Fn(&Path) -> bool,
+    options: &AnalysisOptions,
+) -> Vec<FunctionDef> {
     let def_sites = scan.def_sites();
     let mut result = Vec::new();
     for fd in &scan.definitions {
-        if is_engine_callback(&fd.name) {
+        // Engine callbacks and `@rpc` methods run outside the project's own code.
+        if is_engine_callback(&fd.name) || fd.is_rpc {
             continue;
         }
         if is_test_path(&fd.file) {
@@ -46,7 +59,7 @@ pub fn find_only_test_referenced_functions(
         if fd.ignore_dead_code {
             continue;
         }
-        let refs = scan.refs_excluding_def_sites(&fd.name, &def_sites);
+        let refs = counted_refs(scan, fd, &def_sites, options);
         if refs.is_empty() {
             continue;
         }
@@ -1,14 +1,18 @@
 //! Find function definitions that are never referenced.
 
-use std::path::Path;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
-use super::super::constants::{is_engine_callback, is_gut_test_function};
-use super::super::models::{FunctionDef, ScanResult};
+use super::super::constants::is_engine_callback;
+use super::super::models::{FunctionDef, RefKind, RefSite, ScanResult};
 use super::super::scan::scan_directory;
+use super::AnalysisOptions;
 
 /// Return list of function definitions that are never referenced.
-/// Engine callbacks (e.g. _ready) are always considered used.
-/// References on the same file:line as a definition (the def line itself) are ignored.
+/// Engine callbacks (e.g. _ready) and `@rpc` methods are always considered used (as are
+/// [`AnalysisOptions::virtual_methods`] with [`find_unused_functions_with`]).
+/// References on the same file:line as a definition (the def line itself) are ignored, as are calls
+/// known to go to another script (an autoload singleton, see `ScanOptions::autoloads`).
 pub fn find_unused_functions(
     root: &Path,
     scan: Option<&ScanResult>,
@@ -22,22 +26,92 @@ pub fn find_unused_functions(
             return find_unused_functions(root, Some(&s), exclude_dirs);
         }
     };
+    find_unused_functions_with(scan, &AnalysisOptions::default())
+}
+
+/// Like [`find_unused_functions`] over an existing scan, with analysis options (e.g. scoped matching).
+pub fn find_unused_functions_with(
+    scan: &ScanResult,
+    options: &AnalysisOptions,
+) -> Vec<FunctionDef> {
     let def_sites = scan.def_sites();
-    let mut unused = Vec::new();
-    for fd in &scan.definitions {
-        if is_engine_callback(&fd.name) {
-            continue;
-        }
-        if is_gut_test_function(&fd.name) {
-            continue;
-        }
-        if fd.ignore_dead_code {
-            continue;
-        }
-        let refs = scan.refs_excluding_def_sites(&fd.name, &def_sites);
-        if refs.is_empty() {
-            unused.push(fd.clone());
+    scan.definitions
+        .iter()
+        .filter(|fd| !is_always_used(fd, options))
+        .filter(|fd| counted_refs(scan, fd, &def_sites, options).is_empty())
+        .cloned()
+        .collect()
+}
+
+/// Definitions never reported whatever references them: engine callbacks, `@rpc` methods, test
+/// functions and hooks, tagged functions, native callbacks, virtual methods and (with
+/// `skip_test_definitions`) functions in test code.
+pub(super) fn is_always_used(fd: &FunctionDef, options: &AnalysisOptions) -> bool {
+    always_used_reason(fd, options).is_some()
+}
+
+/// Why `fd` is never reported as unused whatever references it, if it is (see
+/// [`find_unused_functions_with`]).
+pub fn always_used_reason(fd: &FunctionDef, options: &AnalysisOptions) -> Option<&'static str> {
+    if is_engine_callback(&fd.name) {
+        Some("engine callback")
+    } else if options.virtual_methods.contains(&fd.name) {
+        Some("virtual method (--virtual)")
+    } else if fd.is_rpc {
+        Some("@rpc method, called remotely")
+    } else if is_test_framework_function(fd, options) {
+        Some("test function or lifecycle hook run by the test framework")
+    } else if fd.ignore_dead_code {
+        Some("tagged with an ignore comment (e.g. # gdcf-ignore)")
+    } else if options.native_callbacks.contains(&fd.name) {
+        Some("native callback (--native-callback)")
+    } else if options
+        .skip_test_definitions
+        .as_ref()
+        .is_some_and(|is_test_path| is_test_path(&fd.file))
+    {
+        Some("defined in test code (--skip-test-definitions)")
+    } else {
+        None
+    }
+}
+
+/// True if the test framework runs `fd`: a hook, or a `test_*` method (in test code only with
+/// `strict_tests`).
+fn is_test_framework_function(fd: &FunctionDef, options: &AnalysisOptions) -> bool {
+    let framework = options.test_framework;
+    match &options.strict_tests {
+        Some(is_test_path) => {
+            framework.is_hook(&fd.name)
+                || framework.is_test_function(&fd.name) && is_test_path(&fd.file)
         }
+        None => framework.is_test_function(&fd.name),
+    }
+}
+
+/// References that keep `fd` alive under `options`, i.e. what [`find_unused_functions_with`]
+/// counts (definition lines excluded).
+pub fn counted_references(
+    scan: &ScanResult,
+    fd: &FunctionDef,
+    options: &AnalysisOptions,
+) -> Vec<RefSite> {
+    counted_refs(scan, fd, &scan.def_sites(), options)
+}
+
+/// References that count for `fd` under `options` (confidence threshold, scoped matching).
+pub(super) fn counted_refs(
+    scan: &ScanResult,
+    fd: &FunctionDef,
+    def_sites: &HashSet<(PathBuf, u32, String)>,
+    options: &AnalysisOptions,
+) -> Vec<RefSite> {
+    let mut refs = scan.refs_for(fd, def_sites);
+    if let Some(min) = options.min_confidence {
+        refs.retain(|r| r.confidence >= min);
+    }
+    if options.scoped {
+        refs.retain(|r| r.kind == RefKind::Qualified || scan.references_script(&r.path, &fd.file));
     }
-    unused
+    refs
 }
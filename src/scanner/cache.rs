@@ -0,0 +1,254 @@
+//! Incremental scan cache: per-file parse results keyed by modification time and size, with the
+//! content hash as a fallback (a touched but unchanged file is not re-parsed either).
+//!
+//! The whole cache is tagged with a fingerprint of the effective analysis config; when the
+//! fingerprint changes (e.g. different excludes), every entry is discarded.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+use super::constants::{ENGINE_CALLBACKS, GDUNIT4_HOOKS, GUT_HOOKS};
+use super::gd_definitions::{IGNORE_DEAD_CODE_PATTERN, IGNORE_FILE_MARKER};
+use super::models::{
+    AliasTarget, ConstDef, EnumMemberDef, FunctionDef, Reference, ScriptRefs, SignalDef,
+};
+use super::scan::{gdscript_suffixes, ScanOptions, DEFAULT_NOREF_PATTERN};
+
+/// Cache file name inside the cache directory.
+const CACHE_FILE: &str = "scan-cache.json";
+
+/// Parse results for a single file (what the cache stores per entry).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct FileScan {
+    pub definitions: Vec<FunctionDef>,
+    pub references: Vec<Reference>,
+    pub class_name: Option<String>,
+    pub const_aliases: Vec<(String, AliasTarget)>,
+    pub script_refs: Option<ScriptRefs>,
+    pub signals: Vec<SignalDef>,
+    pub signal_references: Vec<(String, u32)>,
+    pub consts: Vec<ConstDef>,
+    pub enum_members: Vec<EnumMemberDef>,
+    pub tokens: Vec<(String, u32)>,
+    /// `Class.method` values: (class identifier, method reference), resolved after the scan.
+    pub static_method_values: Vec<(String, Reference)>,
+    /// `self.member` / `obj.member` values, filtered to known function names like other loose
+    /// references.
+    pub member_values: Vec<Reference>,
+    /// Calls of Callables looked up by key: (line, call text).
+    pub dynamic_calls: Vec<(u32, String)>,
+    /// Method calls on capitalized identifiers: (receiver, method reference), collected with
+    /// `ScanOptions::autoloads` and resolved after the scan.
+    pub receiver_calls: Vec<(String, Reference)>,
+}
+
+/// Modification time and size of a file: when both match the cache, the file is not even read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct FileStamp {
+    mtime_ns: u64,
+    size: u64,
+}
+
+impl FileStamp {
+    /// Stamp of the file at `path`; `None` if its metadata or mtime is unavailable.
+    pub fn of(path: &Path) -> Option<Self> {
+        let meta = std::fs::metadata(path).ok()?;
+        let mtime = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some(FileStamp {
+            mtime_ns: mtime.as_nanos() as u64,
+            size: meta.len(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    content_hash: u64,
+    #[serde(default)]
+    stamp: Option<FileStamp>,
+    scan: FileScan,
+}
+
+/// On-disk cache of per-file scan results.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ScanCache {
+    config_hash: u64,
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+/// 64-bit FNV-1a: stable across runs and Rust versions (unlike `DefaultHasher`).
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in bytes {
+        hash ^= u64::from(*b);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// Fingerprint of everything that affects scan/analysis results besides file contents,
+/// including the canonical `root` that `res://` paths in cached entries were resolved against.
+pub(crate) fn config_fingerprint(root: &Path, options: &ScanOptions) -> u64 {
+    let mut excludes = options.exclude_dirs.clone();
+    excludes.sort();
+    let mut parts = vec![env!("CARGO_PKG_VERSION").to_string()];
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    parts.push(format!("root={}", root.display()));
+    parts.push(format!("exclude={}", excludes.join(",")));
+    let mut properties = options.tscn_ref_properties.clone();
+    properties.sort();
+    parts.push(format!("tscn_ref_properties={}", properties.join(",")));
+    parts.push(format!("consts={}", options.consts));
+    parts.push(format!("enums={}", options.enums));
+    parts.push(format!("autoloads={}", options.autoloads));
+    parts.push(format!(
+        "extensions={}",
+        gdscript_suffixes(&options.extensions).join(",")
+    ));
+    parts.push(format!(
+        "noref={}",
+        options
+            .noref_pattern
+            .as_deref()
+            .unwrap_or(DEFAULT_NOREF_PATTERN)
+    ));
+    parts.push(format!(
+        "no_ignored_file_refs={}",
+        options.no_ignored_file_refs
+    ));
+    parts.push(format!("callbacks={}", ENGINE_CALLBACKS.join(",")));
+    parts.push(format!("gut_hooks={}", GUT_HOOKS.join(",")));
+    parts.push(format!("gdunit4_hooks={}", GDUNIT4_HOOKS.join(",")));
+    parts.push(format!(
+        "markers={},{}",
+        IGNORE_DEAD_CODE_PATTERN, IGNORE_FILE_MARKER
+    ));
+    fnv1a(parts.join("\n").as_bytes())
+}
+
+impl ScanCache {
+    /// Load the cache from `dir`; missing, unreadable or stale (config mismatch) caches start empty.
+    pub fn load(dir: &Path, config_hash: u64) -> Self {
+        let cached = std::fs::read_to_string(dir.join(CACHE_FILE))
+            .ok()
+            .and_then(|text| serde_json::from_str::<ScanCache>(&text).ok());
+        match cached {
+            Some(cache) if cache.config_hash == config_hash => cache,
+            _ => ScanCache {
+                config_hash,
+                entries: HashMap::new(),
+            },
+        }
+    }
+
+    /// Cached scan for `path` if its modification time and size are unchanged.
+    pub fn get_unchanged(&self, path: &Path, stamp: FileStamp) -> Option<&FileScan> {
+        self.entries
+            .get(path)
+            .filter(|e| e.stamp == Some(stamp))
+            .map(|e| &e.scan)
+    }
+
+    /// Cached scan for `path` if its content hash is unchanged.
+    pub fn get(&self, path: &Path, content_hash: u64) -> Option<&FileScan> {
+        self.entries
+            .get(path)
+            .filter(|e| e.content_hash == content_hash)
+            .map(|e| &e.scan)
+    }
+
+    pub fn insert(
+        &mut self,
+        path: PathBuf,
+        content_hash: u64,
+        stamp: Option<FileStamp>,
+        scan: FileScan,
+    ) {
+        let entry = CacheEntry {
+            content_hash,
+            stamp,
+            scan,
+        };
+        self.entries.insert(path, entry);
+    }
+
+    /// Delete the cache file in `dir` (`--clear-cache`); a missing file is not an error.
+    pub fn clear(dir: &Path) -> std::io::Result<()> {
+        match std::fs::remove_file(dir.join(CACHE_FILE)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Drop entries for files not seen in the current scan (deleted or newly excluded).
+    pub fn retain_paths(&mut self, seen: &std::collections::HashSet<PathBuf>) {
+        self.entries.retain(|path, _| seen.contains(path));
+    }
+
+    /// Write the cache to `dir` (created if missing).
+    pub fn save(&self, dir: &Path) -> std::io::Result<()> {
+        std::fs::create_dir_all(dir)?;
+        let text = serde_json::to_string(self)?;
+        std::fs::write(dir.join(CACHE_FILE), text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fnv1a_known_values() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_ne!(fnv1a(b"a"), fnv1a(b"b"));
+    }
+
+    #[test]
+    fn config_fingerprint_ignores_exclude_order() {
+        let options = |exclude_dirs: &[&str], tscn_ref_properties: &[&str]| ScanOptions {
+            exclude_dirs: exclude_dirs.iter().map(|s| s.to_string()).collect(),
+            tscn_ref_properties: tscn_ref_properties.iter().map(|s| s.to_string()).collect(),
+            ..ScanOptions::default()
+        };
+        let root = Path::new("/game");
+        let a = config_fingerprint(root, &options(&["addons", "vendor"], &[]));
+        let b = config_fingerprint(root, &options(&["vendor", "addons"], &[]));
+        assert_eq!(a, b);
+        assert_ne!(a, config_fingerprint(root, &options(&["addons"], &[])));
+        assert_ne!(
+            a,
+            config_fingerprint(root, &options(&["addons", "vendor"], &["cb"]))
+        );
+        let consts = ScanOptions {
+            consts: true,
+            ..options(&["addons", "vendor"], &[])
+        };
+        assert_ne!(a, config_fingerprint(root, &consts));
+    }
+
+    #[test]
+    fn config_fingerprint_depends_on_root() {
+        let options = ScanOptions::default();
+        assert_ne!(
+            config_fingerprint(Path::new("/game"), &options),
+            config_fingerprint(Path::new("/other"), &options)
+        );
+    }
+
+    #[test]
+    fn load_discards_cache_with_other_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cache = ScanCache::load(dir.path(), 1);
+        cache.insert("a.gd".into(), 42, None, FileScan::default());
+        cache.save(dir.path()).unwrap();
+        assert!(ScanCache::load(dir.path(), 1)
+            .get(Path::new("a.gd"), 42)
+            .is_some());
+        assert!(ScanCache::load(dir.path(), 2)
+            .get(Path::new("a.gd"), 42)
+            .is_none());
+    }
+}
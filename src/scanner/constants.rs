@@ -1,7 +1,7 @@
-//! Constants and predicates for engine/GUT callbacks.
+//! Constants and predicates for engine callbacks and test framework (GUT, GdUnit4) hooks.
 
 /// Godot engine callbacks / virtual methods – always considered "used"
-const ENGINE_CALLBACKS: &[&str] = &[
+pub(super) const ENGINE_CALLBACKS: &[&str] = &[
     "_init",
     "_ready",
     "_enter_tree",
@@ -22,7 +22,7 @@ const ENGINE_CALLBACKS: &[&str] = &[
 ];
 
 /// GUT (Godot Unit Test) lifecycle hooks – framework calls these; treat as used
-const GUT_HOOKS: &[&str] = &[
+pub(super) const GUT_HOOKS: &[&str] = &[
     "before_each",
     "after_each",
     "before_all",
@@ -31,11 +31,38 @@ const GUT_HOOKS: &[&str] = &[
     "after_test",
 ];
 
-pub fn is_engine_callback(name: &str) -> bool {
-    ENGINE_CALLBACKS.contains(&name)
+/// GdUnit4 lifecycle hooks – framework calls these; treat as used
+pub(super) const GDUNIT4_HOOKS: &[&str] = &["before", "after", "before_test", "after_test"];
+
+/// Unit test framework whose test methods and lifecycle hooks count as entry points.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum TestFramework {
+    /// GUT: `test_*`, `before_each`, `after_each`, `before_all`, `after_all`, ...
+    Gut,
+    /// GdUnit4: `test_*`, `before`, `after`, `before_test`, `after_test`
+    Gdunit4,
+    /// Hooks of both frameworks.
+    #[default]
+    Both,
+}
+
+impl TestFramework {
+    /// True if the framework runs `name`: a `test_*` method (case-insensitive prefix) or one of
+    /// its lifecycle hooks.
+    pub fn is_test_function(self, name: &str) -> bool {
+        name.len() >= 5 && name[..5].eq_ignore_ascii_case("test_") || self.is_hook(name)
+    }
+
+    /// True if `name` is one of the framework's lifecycle hooks (`before_each`, `after`, ...).
+    pub fn is_hook(self, name: &str) -> bool {
+        match self {
+            TestFramework::Gut => GUT_HOOKS.contains(&name),
+            TestFramework::Gdunit4 => GDUNIT4_HOOKS.contains(&name),
+            TestFramework::Both => GUT_HOOKS.contains(&name) || GDUNIT4_HOOKS.contains(&name),
+        }
+    }
 }
 
-/// True if name is a GUT test method (func test_*) or GUT hook which the framework runs (case-insensitive for test_ prefix).
-pub fn is_gut_test_function(name: &str) -> bool {
-    name.len() >= 5 && name[..5].eq_ignore_ascii_case("test_") || GUT_HOOKS.contains(&name)
+pub fn is_engine_callback(name: &str) -> bool {
+    ENGINE_CALLBACKS.contains(&name)
 }
@@ -1,10 +1,13 @@
 //! File system traversal for .gd and .tscn files.
 
+use std::borrow::Cow;
 use std::collections::HashSet;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
+use regex::Regex;
+
 /// Normalize **/name or path/name to just the directory name (e.g. **/addons -> addons).
 pub fn normalize_exclude_dir(pattern: &str) -> String {
     let normalized = pattern.replace('\\', "/");
@@ -12,73 +15,220 @@ pub fn normalize_exclude_dir(pattern: &str) -> String {
     name.rsplit('/').next().unwrap_or(name).to_string()
 }
 
-fn matches_extension(path: &Path, ext: &str) -> bool {
-    path.file_name()
-        .and_then(|n| n.to_str())
-        .is_some_and(|n| n.to_lowercase().ends_with(ext))
+/// True if an `--exclude-dir`/`--test-dir` value is a glob (e.g. `src/**/generated`,
+/// `**/*_spec.gd`) rather than a plain name, `**/name` or path.
+pub fn is_glob_pattern(pattern: &str) -> bool {
+    pattern
+        .replace('\\', "/")
+        .trim_start_matches("**/")
+        .contains(['*', '?'])
 }
 
-fn walk_files_rec(
-    dir_path: &Path,
-    root_path: &Path,
-    exclude_dirs: &HashSet<String>,
-    result: &mut Vec<PathBuf>,
-    extension: &str,
-    debug_out: &mut Option<&mut dyn Write>,
-) {
-    let mut dirs = Vec::new();
-    let mut files = Vec::new();
-    let read_dir = match fs::read_dir(dir_path) {
-        Ok(rd) => rd,
-        Err(_) => return,
-    };
-    for entry in read_dir.flatten() {
-        let path = entry.path();
-        if path.is_dir() {
-            dirs.push(path);
-        } else if path.is_file() {
-            files.push(path);
-        }
-    }
-    if let Some(ref mut out) = debug_out {
-        let matching: Vec<_> = files
+/// Gitignore-style path patterns (e.g. from `.gdcfignore`), matched against paths relative to
+/// the scan root with `/` separators.
+///
+/// `*` and `?` do not cross `/`, `**` does; a trailing `/` matches directories only; a pattern
+/// with no other `/` matches a name at any depth, otherwise it is anchored at the root. An
+/// ignored directory is not descended into. Negation (`!pattern`) is not supported.
+#[derive(Debug, Clone, Default)]
+pub struct IgnorePatterns {
+    /// (compiled pattern, matches directories only)
+    patterns: Vec<(Regex, bool)>,
+}
+
+impl IgnorePatterns {
+    /// Compile glob lines; blank lines are skipped.
+    pub fn new<S: AsRef<str>>(globs: &[S]) -> Self {
+        let patterns = globs
             .iter()
-            .filter(|p| matches_extension(p, extension))
-            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .map(|g| g.as_ref().trim().replace('\\', "/"))
+            .filter(|g| !g.is_empty())
+            .filter_map(|g| {
+                let dir_only = g.ends_with('/');
+                let g = g.trim_end_matches('/');
+                let anchored = g.contains('/');
+                let body = glob_to_regex(g.trim_start_matches('/'));
+                let re = if anchored {
+                    format!("^{}$", body)
+                } else {
+                    format!("^(?:.*/)?{}$", body)
+                };
+                Regex::new(&re).ok().map(|re| (re, dir_only))
+            })
             .collect();
-        let rel = path_diff(dir_path, root_path)
-            .unwrap_or_else(|| dir_path.to_string_lossy().to_string());
-        let _ = writeln!(out, "  [walk] dirpath={:?} (rel={:?})", dir_path, rel);
-        let dir_names: Vec<_> = dirs
+        IgnorePatterns { patterns }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Add `globs` as directory-only patterns (exclude-dir globs never match files).
+    fn add_dir_globs(&mut self, globs: &[&String]) {
+        let dirs: Vec<String> = globs
             .iter()
-            .map(|d| d.file_name().unwrap().to_string_lossy().to_string())
+            .map(|g| format!("{}/", g.trim_end_matches(['/', '\\'])))
             .collect();
-        let file_names: Vec<_> = files
+        self.patterns.extend(IgnorePatterns::new(&dirs).patterns);
+    }
+
+    /// True if `rel` (relative to the root, `/`-separated) matches any pattern.
+    pub fn is_ignored(&self, rel: &str, is_dir: bool) -> bool {
+        self.patterns
             .iter()
-            .map(|f| f.file_name().unwrap().to_string_lossy().to_string())
-            .collect();
-        let _ = writeln!(out, "  [walk]   dirs={:?}", dir_names);
-        let _ = writeln!(out, "  [walk]   files={:?}", file_names);
-        let _ = writeln!(out, "  [walk]   {} here={:?}", extension, matching);
+            .any(|(re, dir_only)| (is_dir || !dir_only) && re.is_match(rel))
+    }
+
+    /// True if file `path` or one of its parent directories below `root` matches, i.e. a walk
+    /// from `root` would have skipped it.
+    pub fn matches_file(&self, path: &Path, root: &Path) -> bool {
+        let rel = rel_slash_path(path, root);
+        rel.match_indices('/')
+            .any(|(i, _)| self.is_ignored(&rel[..i], true))
+            || self.is_ignored(&rel, false)
     }
-    for p in &files {
-        if matches_extension(p, extension) {
-            result.push(p.clone());
+}
+
+/// Translate one glob (without trailing `/`) into a regex body.
+fn glob_to_regex(glob: &str) -> String {
+    let mut out = String::new();
+    let chars: Vec<char> = glob.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                if chars.get(i + 2) == Some(&'/') {
+                    out.push_str("(?:.*/)?");
+                    i += 3;
+                } else {
+                    out.push_str(".*");
+                    i += 2;
+                }
+                continue;
+            }
+            '*' => out.push_str("[^/]*"),
+            '?' => out.push_str("[^/]"),
+            c => out.push_str(&regex::escape(&c.to_string())),
         }
+        i += 1;
     }
-    for d in &dirs {
-        if d.file_name()
-            .and_then(|n| n.to_str())
-            .is_none_or(|n| !exclude_dirs.contains(&n.to_string()))
-        {
-            walk_files_rec(
-                d.as_path(),
-                root_path,
-                exclude_dirs,
-                result,
-                extension,
-                debug_out,
-            );
+    out
+}
+
+/// Path of `path` relative to `root` with `/` separators (for [`IgnorePatterns`]).
+fn rel_slash_path(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn matches_extension(path: &Path, ext: &str) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.to_lowercase().ends_with(ext))
+}
+
+/// Options for directory walks beyond excludes and ignore globs.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WalkOptions {
+    /// Descend into symlinked directories (each real directory is still walked once, so a link
+    /// back into the tree does not loop). Off: symlinked directories are skipped.
+    pub follow_symlinks: bool,
+    /// Walk into hidden directories (name starting with `.`, e.g. `.git`). Off: they are skipped.
+    pub include_hidden: bool,
+}
+
+/// State of one walk: fixed settings plus the real directories and files already visited.
+struct Walk<'a> {
+    root_path: &'a Path,
+    exclude_dirs: &'a HashSet<String>,
+    ignore: &'a IgnorePatterns,
+    extension: &'a str,
+    options: WalkOptions,
+    /// Canonical directories entered so far.
+    visited_dirs: HashSet<PathBuf>,
+    /// Canonical files yielded so far (a file reached through a symlink is yielded once).
+    seen_files: HashSet<PathBuf>,
+}
+
+impl Walk<'_> {
+    fn walk_files_rec(
+        &mut self,
+        dir_path: &Path,
+        result: &mut Vec<PathBuf>,
+        debug_out: &mut Option<&mut dyn Write>,
+    ) {
+        let real_dir = dir_path
+            .canonicalize()
+            .unwrap_or_else(|_| dir_path.to_path_buf());
+        if !self.visited_dirs.insert(real_dir) {
+            return;
+        }
+        let mut dirs = Vec::new();
+        let mut files = Vec::new();
+        let read_dir = match fs::read_dir(dir_path) {
+            Ok(rd) => rd,
+            Err(_) => return,
+        };
+        for entry in read_dir.flatten() {
+            let path = entry.path();
+            let is_dir = path.is_dir();
+            if !self.ignore.is_empty()
+                && self
+                    .ignore
+                    .is_ignored(&rel_slash_path(&path, self.root_path), is_dir)
+            {
+                continue;
+            }
+            let is_symlink = entry.file_type().is_ok_and(|t| t.is_symlink());
+            let is_hidden = entry.file_name().to_string_lossy().starts_with('.');
+            if is_dir {
+                if (!is_symlink || self.options.follow_symlinks)
+                    && (!is_hidden || self.options.include_hidden)
+                {
+                    dirs.push(path);
+                }
+            } else if path.is_file() {
+                files.push(path);
+            }
+        }
+        if let Some(ref mut out) = debug_out {
+            let matching: Vec<_> = files
+                .iter()
+                .filter(|p| matches_extension(p, self.extension))
+                .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+                .collect();
+            let rel = path_diff(dir_path, self.root_path)
+                .unwrap_or_else(|| dir_path.to_string_lossy().to_string());
+            let _ = writeln!(out, "  [walk] dirpath={:?} (rel={:?})", dir_path, rel);
+            let dir_names: Vec<_> = dirs
+                .iter()
+                .map(|d| d.file_name().unwrap().to_string_lossy().to_string())
+                .collect();
+            let file_names: Vec<_> = files
+                .iter()
+                .map(|f| f.file_name().unwrap().to_string_lossy().to_string())
+                .collect();
+            let _ = writeln!(out, "  [walk]   dirs={:?}", dir_names);
+            let _ = writeln!(out, "  [walk]   files={:?}", file_names);
+            let _ = writeln!(out, "  [walk]   {} here={:?}", self.extension, matching);
+        }
+        for p in &files {
+            if matches_extension(p, self.extension) {
+                let real = p.canonicalize().unwrap_or_else(|_| p.clone());
+                if self.seen_files.insert(real) {
+                    result.push(p.clone());
+                }
+            }
+        }
+        for d in &dirs {
+            if d.file_name()
+                .and_then(|n| n.to_str())
+                .is_none_or(|n| !self.exclude_dirs.contains(&n.to_string()))
+            {
+                self.walk_files_rec(d.as_path(), result, debug_out);
+            }
         }
     }
 }
@@ -102,39 +252,108 @@ fn path_diff(a: &Path, b: &Path) -> Option<String> {
     Some(result.to_string_lossy().to_string())
 }
 
-/// Recursively yield all files under root with the given extension (case-insensitive).
-fn iter_files_by_extension(
+/// Split excludes into directory names (normalized) and globs; the globs are returned both as
+/// given and merged into `ignore` as directory-only patterns.
+fn split_excludes<'a>(
+    exclude_dirs: &'a [String],
+    ignore: &'a IgnorePatterns,
+) -> (HashSet<String>, Vec<&'a String>, Cow<'a, IgnorePatterns>) {
+    let (exclude_globs, exclude_names): (Vec<&String>, Vec<&String>) =
+        exclude_dirs.iter().partition(|p| is_glob_pattern(p));
+    let excluded = exclude_names
+        .iter()
+        .map(|p| normalize_exclude_dir(p))
+        .collect();
+    let ignore = if exclude_globs.is_empty() {
+        Cow::Borrowed(ignore)
+    } else {
+        let mut combined = ignore.clone();
+        combined.add_dir_globs(&exclude_globs);
+        Cow::Owned(combined)
+    };
+    (excluded, exclude_globs, ignore)
+}
+
+/// True if a walk of `root` (see [`iter_files_by_extension`]) would reach `path`: every directory
+/// on the way is entered and the file itself is not ignored. The extension is not checked, and
+/// symlinked directories count as entered. Used to filter file-change events like the scan.
+pub fn is_walked_path(
+    root: &Path,
+    path: &Path,
+    exclude_dirs: &[String],
+    ignore: &IgnorePatterns,
+    options: WalkOptions,
+) -> bool {
+    let root_path = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let Some(rel) = path
+        .strip_prefix(&root_path)
+        .or_else(|_| path.strip_prefix(root))
+        .ok()
+    else {
+        return false;
+    };
+    let (excluded, _, ignore) = split_excludes(exclude_dirs, ignore);
+    let rel_slash = rel.to_string_lossy().replace('\\', "/");
+    let names: Vec<&str> = rel_slash.split('/').filter(|n| !n.is_empty()).collect();
+    let Some((_, dirs)) = names.split_last() else {
+        return false;
+    };
+    let mut prefix = String::new();
+    for dir in dirs {
+        if !prefix.is_empty() {
+            prefix.push('/');
+        }
+        prefix.push_str(dir);
+        if excluded.contains(*dir)
+            || (dir.starts_with('.') && !options.include_hidden)
+            || ignore.is_ignored(&prefix, true)
+        {
+            return false;
+        }
+    }
+    !ignore.is_ignored(&rel_slash, false)
+}
+
+/// Recursively yield all files under root with the given extension (case-insensitive), skipping
+/// excluded directories and paths matching `ignore`. An exclude is a directory name (`addons`,
+/// `**/addons`) or, when it contains `*`/`?` beyond a leading `**/`, a glob matched against the
+/// root-relative directory path (`src/**/generated`). Each real file is yielded once, even when
+/// symlinks reach it twice.
+pub fn iter_files_by_extension(
     root: &Path,
     debug_out: &mut Option<&mut dyn Write>,
     exclude_dirs: Option<&[String]>,
+    ignore: &IgnorePatterns,
     extension: &str,
+    options: WalkOptions,
 ) -> Vec<PathBuf> {
     let root_path = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
-    let excluded: HashSet<String> = exclude_dirs
-        .unwrap_or(&[])
-        .iter()
-        .map(|p| normalize_exclude_dir(p))
-        .collect();
+    let (excluded, exclude_globs, ignore) = split_excludes(exclude_dirs.unwrap_or(&[]), ignore);
     if let Some(out) = debug_out.as_mut() {
         let _ = writeln!(out, "  [walk] root={:?}", root_path);
         let _ = writeln!(out, "  [walk] cwd={:?}", std::env::current_dir().ok());
         let mut sorted: Vec<_> = excluded.iter().collect();
         sorted.sort();
         let _ = writeln!(out, "  [walk] exclude_dirs={:?}", sorted);
+        if !exclude_globs.is_empty() {
+            let _ = writeln!(out, "  [walk] exclude_globs={:?}", exclude_globs);
+        }
         let _ = writeln!(out, "  [walk] root.is_dir()={}", root_path.is_dir());
     }
     if !root_path.is_dir() {
         return Vec::new();
     }
     let mut result = Vec::new();
-    walk_files_rec(
-        &root_path,
-        &root_path,
-        &excluded,
-        &mut result,
+    let mut walk = Walk {
+        root_path: &root_path,
+        exclude_dirs: &excluded,
+        ignore: &ignore,
         extension,
-        debug_out,
-    );
+        options,
+        visited_dirs: HashSet::new(),
+        seen_files: HashSet::new(),
+    };
+    walk.walk_files_rec(&root_path, &mut result, debug_out);
     result
 }
 
@@ -144,7 +363,14 @@ pub fn iter_gd_files(
     debug_out: &mut Option<&mut dyn Write>,
     exclude_dirs: Option<&[String]>,
 ) -> Vec<PathBuf> {
-    iter_files_by_extension(root, debug_out, exclude_dirs, ".gd")
+    iter_files_by_extension(
+        root,
+        debug_out,
+        exclude_dirs,
+        &IgnorePatterns::default(),
+        ".gd",
+        WalkOptions::default(),
+    )
 }
 
 /// Recursively yield all .tscn files under root (case-insensitive).
@@ -153,7 +379,30 @@ pub fn iter_tscn_files(
     debug_out: &mut Option<&mut dyn Write>,
     exclude_dirs: Option<&[String]>,
 ) -> Vec<PathBuf> {
-    iter_files_by_extension(root, debug_out, exclude_dirs, ".tscn")
+    iter_files_by_extension(
+        root,
+        debug_out,
+        exclude_dirs,
+        &IgnorePatterns::default(),
+        ".tscn",
+        WalkOptions::default(),
+    )
+}
+
+/// Recursively yield all .tres files under root (case-insensitive).
+pub fn iter_tres_files(
+    root: &Path,
+    debug_out: &mut Option<&mut dyn Write>,
+    exclude_dirs: Option<&[String]>,
+) -> Vec<PathBuf> {
+    iter_files_by_extension(
+        root,
+        debug_out,
+        exclude_dirs,
+        &IgnorePatterns::default(),
+        ".tres",
+        WalkOptions::default(),
+    )
 }
 
 #[cfg(test)]
@@ -181,6 +430,79 @@ mod tests {
         assert_eq!(normalize_exclude_dir("foo\\addons"), "addons");
     }
 
+    #[test]
+    fn is_walked_path_follows_walk_filters() {
+        let root = Path::new("/game");
+        let excludes = vec!["addons".to_string(), "src/**/generated".to_string()];
+        let ignore = IgnorePatterns::new(&["*_old.gd"]);
+        let walked = |rel: &str, options: WalkOptions| {
+            is_walked_path(root, &root.join(rel), &excludes, &ignore, options)
+        };
+        let default = WalkOptions::default();
+        assert!(walked("player.gd", default));
+        assert!(walked("scenes/level.tscn", default));
+        assert!(!walked("addons/gut/test.gd", default));
+        assert!(!walked("src/a/generated/x.gd", default));
+        assert!(!walked("enemy_old.gd", default));
+        assert!(!walked(".godot/imported/x.gd", default));
+        let hidden = WalkOptions {
+            include_hidden: true,
+            ..default
+        };
+        assert!(walked(".godot/imported/x.gd", hidden));
+        assert!(!is_walked_path(
+            root,
+            Path::new("/elsewhere/x.gd"),
+            &excludes,
+            &ignore,
+            default
+        ));
+    }
+
+    #[test]
+    fn ignore_patterns_gitignore_style() {
+        let ignore = IgnorePatterns::new(&["generated/", "*_old.gd", "/scripts/tmp", "a/**/z.gd"]);
+        assert!(ignore.is_ignored("generated", true));
+        assert!(ignore.is_ignored("src/generated", true));
+        assert!(!ignore.is_ignored("generated", false));
+        assert!(ignore.is_ignored("player_old.gd", false));
+        assert!(ignore.is_ignored("src/player_old.gd", false));
+        assert!(!ignore.is_ignored("src/player.gd", false));
+        assert!(ignore.is_ignored("scripts/tmp", true));
+        assert!(!ignore.is_ignored("other/scripts/tmp", true));
+        let root = Path::new("/project");
+        assert!(ignore.matches_file(Path::new("/project/src/generated/api.gd"), root));
+        assert!(ignore.matches_file(Path::new("/project/src/player_old.gd"), root));
+        assert!(!ignore.matches_file(Path::new("/project/src/player.gd"), root));
+        assert!(ignore.is_ignored("a/z.gd", false));
+        assert!(ignore.is_ignored("a/b/c/z.gd", false));
+        assert!(!IgnorePatterns::default().is_ignored("x.gd", false));
+    }
+
+    #[test]
+    fn iter_files_by_extension_skips_ignored_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("gen/deep")).unwrap();
+        std::fs::write(root.join("main.gd"), "").unwrap();
+        std::fs::write(root.join("main_old.gd"), "").unwrap();
+        std::fs::write(root.join("gen/deep/x.gd"), "").unwrap();
+        let ignore = IgnorePatterns::new(&["gen/", "*_old.gd"]);
+        let files = iter_files_by_extension(
+            root,
+            &mut None,
+            None,
+            &ignore,
+            ".gd",
+            WalkOptions::default(),
+        );
+        let names: Vec<_> = files
+            .iter()
+            .map(|p| p.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["main.gd"]);
+    }
+
     #[test]
     fn iter_gd_files_empty_dir() {
         let dir = tempfile::tempdir().unwrap();
@@ -224,6 +546,90 @@ mod tests {
         assert!(!names.contains(&"plugin.gd"));
     }
 
+    #[test]
+    fn iter_gd_files_exclude_dir_globs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/ui/generated")).unwrap();
+        std::fs::create_dir_all(root.join("generated")).unwrap();
+        std::fs::create_dir_all(root.join("src/cache_tmp")).unwrap();
+        std::fs::write(root.join("src/ui/generated/api.gd"), "").unwrap();
+        std::fs::write(root.join("generated/top.gd"), "").unwrap();
+        std::fs::write(root.join("src/cache_tmp/c.gd"), "").unwrap();
+        std::fs::write(root.join("src/ui/main.gd"), "").unwrap();
+        let excludes = ["src/**/generated".to_string(), "**/*_tmp".to_string()];
+        let files = iter_gd_files(root, &mut None, Some(&excludes));
+        let mut names: Vec<_> = files
+            .iter()
+            .map(|p| p.file_name().unwrap().to_str().unwrap())
+            .collect();
+        names.sort();
+        // `src/**/generated` is anchored at the root: top-level generated/ is kept.
+        assert_eq!(names, vec!["main.gd", "top.gd"]);
+        assert!(is_glob_pattern("src/**/generated"));
+        assert!(!is_glob_pattern("**/addons"));
+        assert!(!is_glob_pattern("foo/addons"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn iter_files_by_extension_self_referential_symlink() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/lib")).unwrap();
+        std::fs::write(root.join("src/a.gd"), "").unwrap();
+        std::fs::write(root.join("src/lib/b.gd"), "").unwrap();
+        std::os::unix::fs::symlink(root, root.join("src/loop")).unwrap();
+        std::os::unix::fs::symlink(root.join("src/lib"), root.join("lib_link")).unwrap();
+        std::os::unix::fs::symlink(root.join("src/a.gd"), root.join("a_link.gd")).unwrap();
+        let shared = tempfile::tempdir().unwrap();
+        std::fs::write(shared.path().join("c.gd"), "").unwrap();
+        std::os::unix::fs::symlink(shared.path(), root.join("shared")).unwrap();
+        let names = |follow_symlinks| {
+            let options = WalkOptions {
+                follow_symlinks,
+                ..WalkOptions::default()
+            };
+            let ignore = IgnorePatterns::default();
+            let files = iter_files_by_extension(root, &mut None, None, &ignore, ".gd", options);
+            let mut names: Vec<_> = files
+                .iter()
+                .map(|p| p.canonicalize().unwrap())
+                .map(|p| p.file_name().unwrap().to_str().unwrap().to_string())
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names(false), vec!["a.gd", "b.gd"]);
+        assert_eq!(names(true), vec!["a.gd", "b.gd", "c.gd"]);
+    }
+
+    #[test]
+    fn iter_files_by_extension_skips_hidden_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join(".git/hooks")).unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join(".git/hooks/x.gd"), "").unwrap();
+        std::fs::write(root.join("src/main.gd"), "").unwrap();
+        let names = |include_hidden| {
+            let options = WalkOptions {
+                include_hidden,
+                ..WalkOptions::default()
+            };
+            let ignore = IgnorePatterns::default();
+            let files = iter_files_by_extension(root, &mut None, None, &ignore, ".gd", options);
+            let mut names: Vec<_> = files
+                .iter()
+                .map(|p| p.file_name().unwrap().to_str().unwrap().to_string())
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names(false), vec!["main.gd"]);
+        assert_eq!(names(true), vec!["main.gd", "x.gd"]);
+    }
+
     #[test]
     fn iter_gd_files_with_debug_out() {
         let dir = tempfile::tempdir().unwrap();
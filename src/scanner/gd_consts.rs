@@ -0,0 +1,97 @@
+//! Extract `const` declarations and identifier tokens (candidate constant uses) from GDScript source.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use super::gd_definitions::{line_has_ignore_marker, strip_comments, strip_string_literals};
+use super::models::ConstDef;
+
+/// `const NAME = ...`, `const NAME: Type = ...` or `const NAME := ...` at the start of a line.
+static CONST_DEF_RE: OnceLock<Regex> = OnceLock::new();
+
+/// Any identifier token.
+static IDENT_RE: OnceLock<Regex> = OnceLock::new();
+
+fn const_def_re() -> &'static Regex {
+    CONST_DEF_RE.get_or_init(|| {
+        Regex::new(r"(?m)^[ \t]*const[ \t]+([a-zA-Z_][a-zA-Z0-9_]*)[ \t]*(?::[^=\n]*)?=").unwrap()
+    })
+}
+
+/// Extract all constant declarations from a GDScript source (top-level and inner classes).
+/// Declarations tagged with an ignore marker (e.g. `# gdcf-ignore`) on the same line get
+/// `ignore_dead_code: true`.
+pub fn find_const_definitions(path: &Path, source: &str) -> Vec<ConstDef> {
+    let masked = strip_string_literals(&strip_comments(source));
+    let mut out = Vec::new();
+    for cap in const_def_re().captures_iter(&masked) {
+        let m = cap.get(1).unwrap();
+        let line_start = source[..m.start()].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[m.end()..]
+            .find('\n')
+            .map_or(source.len(), |i| m.end() + i);
+        out.push(ConstDef {
+            name: m.as_str().to_string(),
+            file: path.to_path_buf(),
+            line: (source[..m.start()].matches('\n').count() + 1) as u32,
+            ignore_dead_code: line_has_ignore_marker(&source[line_start..line_end]),
+        });
+    }
+    out
+}
+
+/// Every identifier token outside comments and string literals, once per (name, line). Any of
+/// them may name a constant: bare `MAX_SPEED` or qualified `Config.MAX_SPEED`.
+pub fn find_identifier_tokens(_path: &Path, source: &str) -> Vec<(String, u32)> {
+    let masked = strip_string_literals(&strip_comments(source));
+    let re = IDENT_RE.get_or_init(|| Regex::new(r"\b[a-zA-Z_][a-zA-Z0-9_]*").unwrap());
+    let mut seen = HashSet::new();
+    let mut line = 1u32;
+    let mut last = 0usize;
+    let mut out = Vec::new();
+    for m in re.find_iter(&masked) {
+        line += masked[last..m.start()].matches('\n').count() as u32;
+        last = m.start();
+        if seen.insert((m.as_str(), line)) {
+            out.push((m.as_str().to_string(), line));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_const_definitions_plain_typed_and_inferred() {
+        let src = "const A = 1\nconst B: int = 2\nconst C := 3\n# const D = 4\nvar E = 5\nclass Inner:\n    const F = \"x\" # gdcf-ignore\n";
+        let defs = find_const_definitions(Path::new("a.gd"), src);
+        let got: Vec<_> = defs
+            .iter()
+            .map(|d| (d.name.as_str(), d.line, d.ignore_dead_code))
+            .collect();
+        assert_eq!(
+            got,
+            vec![
+                ("A", 1, false),
+                ("B", 2, false),
+                ("C", 3, false),
+                ("F", 7, true)
+            ]
+        );
+    }
+
+    #[test]
+    fn find_identifier_tokens_skips_strings_and_comments() {
+        let src = "var x = LIMIT # OTHER\nprint(\"LIMIT2\", Config.MAX)\n";
+        let tokens = find_identifier_tokens(Path::new("a.gd"), src);
+        let names: Vec<_> = tokens.iter().map(|(n, l)| (n.as_str(), *l)).collect();
+        assert!(names.contains(&("LIMIT", 1)));
+        assert!(names.contains(&("MAX", 2)));
+        assert!(!names.iter().any(|(n, _)| *n == "OTHER" || *n == "LIMIT2"));
+    }
+}
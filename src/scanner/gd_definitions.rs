@@ -6,35 +6,161 @@ use std::sync::OnceLock;
 use regex::Regex;
 
 use super::models::FunctionDef;
+use super::util::line_col;
 
-/// func name( ... ): or static func name( ... ):
-/// Optional: -> Type at end. Name is identifier (letters, digits, underscore)
-static FUNC_DEF_RE: OnceLock<Regex> = OnceLock::new();
+/// Start of a definition up to the opening paren: `func name(` or `static func name(`.
+/// The parameter list (which may span lines and contain nested parens) is balanced separately,
+/// then an optional `-> Type` and the closing `:` are required.
+static FUNC_HEAD_RE: OnceLock<Regex> = OnceLock::new();
+
+/// class_name Name (optionally followed by `, "icon.svg"` in Godot 3)
+static CLASS_NAME_RE: OnceLock<Regex> = OnceLock::new();
+/// Inner class declaration: `class Name:` or `class Name extends Base:`
+static INNER_CLASS_RE: OnceLock<Regex> = OnceLock::new();
 
 /// Regex: # then optional space then gdcf-ignore | dead-code-ignore | TODO: dead-code (case-insensitive).
+pub(super) const IGNORE_DEAD_CODE_PATTERN: &str =
+    r"(?i)#\s*(?:gdcf-ignore|dead-code-ignore|TODO:\s*dead-code)";
 static IGNORE_DEAD_CODE_RE: OnceLock<Regex> = OnceLock::new();
 
-fn func_def_re() -> &'static Regex {
-    FUNC_DEF_RE.get_or_init(|| {
-        Regex::new(
-            r"(?m)^\s*(?:static\s+)?func\s+([a-zA-Z_][a-zA-Z0-9_]*)\s*\([^)]*\)\s*(?:->[^:]+)?\s*:",
-        )
-        .unwrap()
+/// File-level marker: a `# gdcf-ignore-file` comment within the first
+/// [`IGNORE_FILE_MARKER_LINES`] lines keeps the whole file's definitions out of the report.
+pub(super) const IGNORE_FILE_MARKER: &str = "gdcf-ignore-file";
+pub const IGNORE_FILE_MARKER_LINES: usize = 5;
+
+fn func_head_re() -> &'static Regex {
+    FUNC_HEAD_RE.get_or_init(|| {
+        Regex::new(r"(?m)^[ \t]*(static[ \t]+)?func[ \t]+([a-zA-Z_][a-zA-Z0-9_]*)[ \t]*\(").unwrap()
     })
 }
 
+/// Given `masked` source (strings and comments blanked) and the index just after a definition's
+/// opening paren, return the index just after the header's closing `:`, or None if the
+/// parentheses never balance or no `:` follows (optionally after `-> Type`).
+fn header_end(masked: &[u8], after_open: usize) -> Option<usize> {
+    let mut depth = 1usize;
+    let mut i = after_open;
+    while i < masked.len() && depth > 0 {
+        match masked[i] {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth -= 1,
+            _ => {}
+        }
+        i += 1;
+    }
+    if depth > 0 {
+        return None;
+    }
+    // After `)`: optional `-> Type` on the same logical line, then `:`.
+    let rest = &masked[i..];
+    let mut j = rest.iter().position(|b| !matches!(b, b' ' | b'\t'))?;
+    if rest[j..].starts_with(b"->") {
+        j += 2;
+        while j < rest.len() && !matches!(rest[j], b':' | b'\n') {
+            j += 1;
+        }
+    }
+    (rest.get(j) == Some(&b':')).then_some(i + j + 1)
+}
+
+/// Last line (1-based) of the body of a function whose `func` line has `func_indent` leading
+/// whitespace chars and whose header ends on `header_line`: the last non-blank line indented
+/// deeper than the `func` line before indentation returns to its level. Blank and comment-only
+/// lines (already blanked in `masked_lines`) neither extend nor end the body.
+fn body_end_line(masked_lines: &[&str], func_indent: usize, header_line: usize) -> u32 {
+    let mut end = header_line;
+    for (i, line) in masked_lines.iter().enumerate().skip(header_line) {
+        let trimmed = line.trim_start();
+        if trimmed.trim_end().is_empty() {
+            continue;
+        }
+        if line.len() - trimmed.len() <= func_indent {
+            break;
+        }
+        end = i + 1;
+    }
+    end as u32
+}
+
+/// True if the annotation lines directly above the `func` on 1-based `func_line` (comments and
+/// strings already blanked in `masked_lines`) include `@rpc` or `@rpc(...)`.
+fn has_rpc_annotation(masked_lines: &[&str], func_line: usize) -> bool {
+    let Some(above) = masked_lines.get(..func_line.saturating_sub(1)) else {
+        return false;
+    };
+    above
+        .iter()
+        .rev()
+        .map(|line| line.trim())
+        .take_while(|line| line.starts_with('@'))
+        .any(|line| {
+            line.strip_prefix("@rpc").is_some_and(|rest| {
+                !rest.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_')
+            })
+        })
+}
+
+/// Inner classes enclosing the `func` on 1-based `func_line`, outermost first and joined with `.`;
+/// `None` at file scope. The nearest less-indented non-blank line above a definition opens its
+/// enclosing block (comments and strings already blanked in `masked_lines`).
+fn enclosing_class(masked_lines: &[&str], func_line: usize) -> Option<String> {
+    let re = INNER_CLASS_RE
+        .get_or_init(|| Regex::new(r"^[ \t]*class[ \t]+([a-zA-Z_][a-zA-Z0-9_]*)\b").unwrap());
+    let indent = |line: &str| line.len() - line.trim_start().len();
+    let func_index = func_line.checked_sub(1)?;
+    let mut limit = indent(masked_lines.get(func_index)?);
+    let mut classes = Vec::new();
+    for line in masked_lines[..func_index].iter().rev() {
+        if limit == 0 {
+            break;
+        }
+        if line.trim().is_empty() || indent(line) >= limit {
+            continue;
+        }
+        match re.captures(line) {
+            Some(cap) => {
+                classes.push(cap[1].to_string());
+                limit = indent(line);
+            }
+            None => break,
+        }
+    }
+    classes.reverse();
+    (!classes.is_empty()).then(|| classes.join("."))
+}
+
 fn ignore_dead_code_re() -> &'static Regex {
-    IGNORE_DEAD_CODE_RE.get_or_init(|| {
-        Regex::new(r"(?i)#\s*(?:gdcf-ignore|dead-code-ignore|TODO:\s*dead-code)").unwrap()
+    IGNORE_DEAD_CODE_RE.get_or_init(|| Regex::new(IGNORE_DEAD_CODE_PATTERN).unwrap())
+}
+
+/// True if a `# gdcf-ignore-file` comment line (case-insensitive) appears within the first
+/// [`IGNORE_FILE_MARKER_LINES`] lines, e.g. at the top of a generated file.
+pub fn has_ignore_file_marker(source: &str) -> bool {
+    source.lines().take(IGNORE_FILE_MARKER_LINES).any(|line| {
+        line.trim_start().strip_prefix('#').is_some_and(|rest| {
+            rest.trim_start()
+                .get(..IGNORE_FILE_MARKER.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(IGNORE_FILE_MARKER))
+        })
     })
 }
 
 /// True if line contains an ignore-dead-code marker after a #.
-fn line_has_ignore_marker(line: &str) -> bool {
+pub(super) fn line_has_ignore_marker(line: &str) -> bool {
     ignore_dead_code_re().is_match(line)
 }
 
-/// Replace string literal contents with spaces so we don't match inside strings.
+/// Return the script's global `class_name`, if declared.
+pub fn find_class_name(source: &str) -> Option<String> {
+    let re = CLASS_NAME_RE
+        .get_or_init(|| Regex::new(r"(?m)^\s*class_name\s+([a-zA-Z_][a-zA-Z0-9_]*)").unwrap());
+    re.captures(source).map(|cap| cap[1].to_string())
+}
+
+/// Replace string literal contents with spaces so we don't match inside strings. A one-char
+/// string prefix (`r"raw"`, `&"StringName"`, `^"NodePath"`) is blanked with the literal.
+/// A quote right after an identifier (`don't`) does not start a string, and an unterminated
+/// `"`/`'` string ends at the end of its line instead of swallowing the rest of the file.
 pub fn strip_string_literals(source: &str) -> String {
     let mut result = String::with_capacity(source.len());
     let mut i = 0;
@@ -42,33 +168,35 @@ pub fn strip_string_literals(source: &str) -> String {
     let bytes = source.as_bytes();
     while i < n {
         let c = bytes[i] as char;
-        if (c == '"' || c == '\'') && (i == 0 || bytes[i - 1] != b'\\') {
+        let after_ident = i > 0
+            && (bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'_')
+            && !has_string_prefix(bytes, i);
+        if (c == '"' || c == '\'') && (i == 0 || bytes[i - 1] != b'\\') && !after_ident {
             let quote = c;
+            if has_string_prefix(bytes, i) {
+                result.pop();
+                result.push(' ');
+            }
             result.push(quote);
             i += 1;
-            if i < n {
-                let next = bytes[i] as char;
-                if next == quote {
-                    result.push(quote);
-                    i += 1;
-                    continue;
-                }
-                if i + 2 <= n {
-                    let two = std::str::from_utf8(&bytes[i..i + 2]).unwrap_or("");
-                    if two == format!("{}{}", quote, quote) {
-                        let triple = format!("{}{}{}", quote, quote, quote);
-                        let rest = std::str::from_utf8(&bytes[i + 3..]).unwrap_or("");
-                        if let Some(pos) = rest.find(&triple) {
-                            let end = i + 3 + pos + 3;
-                            result.extend(std::iter::repeat_n(' ', end - i));
-                            i = end;
-                            continue;
-                        } else {
-                            result.extend(std::iter::repeat_n(' ', n - i));
-                            break;
-                        }
-                    }
-                }
+            if i + 2 <= n && bytes[i] == quote as u8 && bytes[i + 1] == quote as u8 {
+                // Triple-quoted (may span lines): blank through the closing triple quote, keeping
+                // newlines so line numbers still match the source.
+                let triple = format!("{}{}{}", quote, quote, quote);
+                let rest = std::str::from_utf8(&bytes[i + 2..]).unwrap_or("");
+                let end = rest.find(&triple).map_or(n, |pos| i + 2 + pos + 3);
+                result.extend(
+                    bytes[i..end]
+                        .iter()
+                        .map(|&b| if b == b'\n' { '\n' } else { ' ' }),
+                );
+                i = end;
+                continue;
+            }
+            if i < n && bytes[i] == quote as u8 {
+                result.push(quote);
+                i += 1;
+                continue;
             }
             while i < n {
                 if bytes[i] == b'\\' && i + 1 < n {
@@ -81,32 +209,75 @@ pub fn strip_string_literals(source: &str) -> String {
                     i += 1;
                     break;
                 }
+                if bytes[i] == b'\n' {
+                    // Unterminated: the newline is kept and scanning resumes on the next line.
+                    break;
+                }
                 result.push(' ');
                 i += 1;
             }
             continue;
         }
-        result.push(c);
-        i += 1;
+        // Copy whole characters so byte offsets match the source (non-ASCII text outside strings).
+        let ch = source[i..].chars().next().unwrap_or(c);
+        result.push(ch);
+        i += ch.len_utf8();
     }
     result
 }
 
+/// True if the quote at `quote_pos` is preceded by a string prefix (`r`, `&`, `^`) that is not the
+/// end of an identifier (`var r = bar"..."` is not a prefix, `r"..."` is).
+fn has_string_prefix(bytes: &[u8], quote_pos: usize) -> bool {
+    let is_ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    quote_pos >= 1
+        && matches!(bytes[quote_pos - 1], b'r' | b'&' | b'^')
+        && (quote_pos < 2 || !is_ident(bytes[quote_pos - 2]))
+}
+
+/// Blank `#` line comments (from `#` to end of line) with spaces so commented-out code is not
+/// scanned. Byte offsets and line numbers are preserved; a `#` inside a string literal is not a comment.
+pub fn strip_comments(source: &str) -> String {
+    let masked = strip_string_literals(source);
+    let mut out = source.as_bytes().to_vec();
+    let mut in_comment = false;
+    for (i, b) in masked.bytes().enumerate() {
+        match b {
+            b'\n' => in_comment = false,
+            b'#' => in_comment = true,
+            _ => {}
+        }
+        if in_comment {
+            out[i] = b' ';
+        }
+    }
+    // Only whole characters between `#` and a newline were blanked, so this stays valid UTF-8.
+    String::from_utf8(out).unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
+}
+
 /// Extract all function definitions from a GDScript source (top-level and inner classes).
 /// Functions tagged with `# gdcf-ignore`, `# dead-code-ignore`, or `# TODO: dead-code`
 /// (on the same line after `:` or on the next line) get `ignore_dead_code: true`.
 pub fn find_function_definitions(path: &Path, source: &str) -> Vec<FunctionDef> {
     let mut out = Vec::new();
-    for cap in func_def_re().captures_iter(source) {
-        let m = cap.get(0).unwrap();
-        let name_match = cap.get(1).unwrap();
-        let line_no = (source[..name_match.start()].matches('\n').count() + 1) as u32;
+    let masked = strip_string_literals(&strip_comments(source));
+    let masked_lines: Vec<&str> = masked.split('\n').collect();
+    for cap in func_head_re().captures_iter(&masked) {
+        let name_match = cap.get(2).unwrap();
+        let Some(rest_start) = header_end(masked.as_bytes(), cap.get(0).unwrap().end()) else {
+            continue;
+        };
+        let (line_no, column) = line_col(source, name_match.start());
         let name = name_match.as_str().to_string();
-        let full = m.as_str();
-        let is_static = full.contains("static");
+        let is_static = cap.get(1).is_some();
+        let is_rpc = has_rpc_annotation(&masked_lines, line_no as usize);
+        let inner_class = enclosing_class(&masked_lines, line_no as usize);
+        let head = cap.get(0).unwrap().as_str();
+        let func_indent = head.len() - head.trim_start().len();
+        let header_line = masked[..rest_start].matches('\n').count() + 1;
+        let end_line = body_end_line(&masked_lines, func_indent, header_line);
 
-        // Same line: from end of match to end of line
-        let rest_start = m.end();
+        // Same line: from end of header (after `:`) to end of line
         let same_line_end = source[rest_start..]
             .find('\n')
             .map(|o| rest_start + o)
@@ -132,8 +303,12 @@ pub fn find_function_definitions(path: &Path, source: &str) -> Vec<FunctionDef>
             name,
             file: path.to_path_buf(),
             line: line_no,
+            column,
+            end_line,
             is_static,
+            is_rpc,
             ignore_dead_code,
+            inner_class,
         });
     }
     out
@@ -152,6 +327,17 @@ mod tests {
         assert!(!out.contains("call") || out.matches("call").count() == 0);
     }
 
+    #[test]
+    fn strip_string_literals_multiline_triple_quote() {
+        let s = "var doc = \"\"\"\nnot_a_call()\n\"\"\"\nreal_call()\nvar e = \"\"\n";
+        let out = strip_string_literals(s);
+        assert_eq!(out.len(), s.len());
+        assert!(!out.contains("not_a_call"));
+        assert!(out.contains("real_call()"));
+        assert!(out.ends_with("var e = \"\"\n"));
+        assert_eq!(out.matches('\n').count(), s.matches('\n').count());
+    }
+
     #[test]
     fn strip_string_literals_escape() {
         let s = r#""a\\b""#;
@@ -159,6 +345,92 @@ mod tests {
         assert_eq!(out.len(), s.len());
     }
 
+    #[test]
+    fn strip_string_literals_blanks_prefixed_strings() {
+        let s = "var a = r\"not_a_call()\"\nvar b = &\"x()\"\nvar c = ^\"Path/y()\"\nfor_r(1)";
+        let out = strip_string_literals(s);
+        assert_eq!(out.len(), s.len());
+        assert!(!out.contains("not_a_call"));
+        assert!(!out.contains("x()"));
+        assert!(!out.contains("y()"));
+        assert!(out.starts_with("var a =  \""), "prefix blanked: {}", out);
+        assert!(out.ends_with("for_r(1)"));
+    }
+
+    #[test]
+    fn strip_string_literals_unterminated_string_ends_at_line_end() {
+        let s = "var s = \"oops\nreal_call()\nvar t = 'also\nother_call()\n";
+        let out = strip_string_literals(s);
+        assert_eq!(out.len(), s.len());
+        assert!(!out.contains("oops"));
+        assert!(out.contains("\nreal_call()\n"));
+        assert!(out.contains("\nother_call()\n"));
+    }
+
+    #[test]
+    fn strip_comments_apostrophe_in_comment_does_not_hide_calls() {
+        let s = "# don't touch\nreal_call()\nvar s = \"it's\" # it's fine\nother_call()\n";
+        let out = strip_comments(s);
+        assert!(out.contains("real_call()"));
+        assert!(out.contains("other_call()"));
+        assert!(!out.contains("touch"));
+    }
+
+    #[test]
+    fn strip_string_literals_keeps_offsets_for_non_ascii() {
+        let s = "var zażółć = \"ąę\" # żółw\nfoo()";
+        let out = strip_string_literals(s);
+        assert_eq!(out.len(), s.len());
+        assert_eq!(out.find("foo"), s.find("foo"));
+    }
+
+    #[test]
+    fn strip_comments_blanks_to_end_of_line() {
+        let s = "helper() # old_helper()\n# gone()\nnext()";
+        let out = strip_comments(s);
+        assert_eq!(out.len(), s.len());
+        assert!(out.contains("helper()"));
+        assert!(!out.contains("old_helper"));
+        assert!(!out.contains("gone"));
+        assert!(out.ends_with("next()"));
+    }
+
+    #[test]
+    fn strip_comments_ignores_hash_in_string() {
+        let s = "var s = \"# not a comment\"; call_me()";
+        let out = strip_comments(s);
+        assert_eq!(out, s);
+    }
+
+    #[test]
+    fn has_ignore_file_marker_only_near_top() {
+        assert!(has_ignore_file_marker(
+            "# Generated by tool\n# gdcf-ignore-file\nextends Node\n"
+        ));
+        assert!(has_ignore_file_marker("#GDCF-IGNORE-FILE\n"));
+        assert!(!has_ignore_file_marker("extends Node\n# gdcf-ignore\n"));
+        let late = format!(
+            "{}# gdcf-ignore-file\n",
+            "\n".repeat(IGNORE_FILE_MARKER_LINES)
+        );
+        assert!(!has_ignore_file_marker(&late));
+        assert!(!has_ignore_file_marker("var s = \"# gdcf-ignore-file\"\n"));
+    }
+
+    #[test]
+    fn has_ignore_file_marker_non_ascii_comment() {
+        assert!(!has_ignore_file_marker(
+            "# aĄęśćźżółń komentarz
+extends Node
+"
+        ));
+        assert!(has_ignore_file_marker(
+            "# zażółć
+# gdcf-ignore-file
+"
+        ));
+    }
+
     #[test]
     fn find_function_definitions_ignore_dead_code_same_line() {
         let source = "func kept_for_later(): # gdcf-ignore\n    pass";
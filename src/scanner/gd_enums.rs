@@ -0,0 +1,78 @@
+//! Extract enum members from GDScript source (named `enum State { IDLE, RUN }` and anonymous
+//! `enum { A, B }`).
+
+use std::path::Path;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use super::gd_definitions::{line_has_ignore_marker, strip_comments, strip_string_literals};
+use super::models::EnumMemberDef;
+
+/// `enum Name {` or `enum {` at the start of a line; members follow up to the matching `}`.
+static ENUM_HEAD_RE: OnceLock<Regex> = OnceLock::new();
+
+/// Member name at the start of a comma-separated entry (`IDLE`, `RUN = 2`).
+static MEMBER_RE: OnceLock<Regex> = OnceLock::new();
+
+/// Extract one definition per enum member (the body may span several lines). Members tagged
+/// with an ignore marker (e.g. `# gdcf-ignore`) on their line get `ignore_dead_code: true`.
+pub fn find_enum_members(path: &Path, source: &str) -> Vec<EnumMemberDef> {
+    let masked = strip_string_literals(&strip_comments(source));
+    let head = ENUM_HEAD_RE.get_or_init(|| {
+        Regex::new(r"(?m)^[ \t]*enum(?:[ \t]+([a-zA-Z_][a-zA-Z0-9_]*))?[ \t]*\{").unwrap()
+    });
+    let member =
+        MEMBER_RE.get_or_init(|| Regex::new(r"^\s*([a-zA-Z_][a-zA-Z0-9_]*)\s*(?:=|$)").unwrap());
+    let source_lines: Vec<&str> = source.split('\n').collect();
+    let line_at = |pos: usize| (masked[..pos].matches('\n').count() + 1) as u32;
+    let mut out = Vec::new();
+    for cap in head.captures_iter(&masked) {
+        let enum_name = cap.get(1).map(|m| m.as_str().to_string());
+        let body_start = cap.get(0).unwrap().end();
+        let Some(body_len) = masked[body_start..].find('}') else {
+            continue;
+        };
+        let mut offset = body_start;
+        for entry in masked[body_start..body_start + body_len].split(',') {
+            if let Some(m) = member.captures(entry).and_then(|c| c.get(1)) {
+                let line = line_at(offset + m.start());
+                let line_text = source_lines.get(line as usize - 1).copied().unwrap_or("");
+                out.push(EnumMemberDef {
+                    name: m.as_str().to_string(),
+                    enum_name: enum_name.clone(),
+                    file: path.to_path_buf(),
+                    line,
+                    ignore_dead_code: line_has_ignore_marker(line_text),
+                });
+            }
+            offset += entry.len() + 1;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_enum_members_named_anonymous_and_multiline() {
+        let src = "enum State { IDLE, RUN = 2, JUMP }\nenum {\n    NORTH,\n    SOUTH, # gdcf-ignore\n}\n# enum Old { GONE }\n";
+        let members = find_enum_members(Path::new("a.gd"), src);
+        let got: Vec<_> = members
+            .iter()
+            .map(|m| (m.qualified_name(), m.line, m.ignore_dead_code))
+            .collect();
+        assert_eq!(
+            got,
+            vec![
+                ("State.IDLE".to_string(), 1, false),
+                ("State.RUN".to_string(), 1, false),
+                ("State.JUMP".to_string(), 1, false),
+                ("NORTH".to_string(), 3, false),
+                ("SOUTH".to_string(), 4, true),
+            ]
+        );
+    }
+}
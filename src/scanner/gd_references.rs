@@ -1,11 +1,14 @@
 //! Find function references in GDScript source.
 
+use std::collections::HashSet;
 use std::path::Path;
 use std::sync::OnceLock;
 
 use regex::Regex;
 
-use super::gd_definitions::strip_string_literals;
+use super::gd_definitions::{strip_comments, strip_string_literals};
+use super::models::{AliasTarget, Confidence, RefKind, Reference, ScriptRefs};
+use super::util::line_col;
 
 static CALL_DOT_RE: OnceLock<Regex> = OnceLock::new();
 static CALL_STANDALONE_RE: OnceLock<Regex> = OnceLock::new();
@@ -13,13 +16,69 @@ static CALL_DEFERRED_RE: OnceLock<Regex> = OnceLock::new();
 static CALL_DOT_DEFERRED_RE: OnceLock<Regex> = OnceLock::new();
 static CALLABLE_RE: OnceLock<Regex> = OnceLock::new();
 static CONNECT_RE: OnceLock<Regex> = OnceLock::new();
+/// Godot 3 style: connect("signal", target, "method") – method name is the third (string) argument
+static CONNECT_LEGACY_RE: OnceLock<Regex> = OnceLock::new();
+/// `.method(` after any receiver expression: `obj.m()`, `get_weapon().m()`, `$Node.m()`,
+/// `get_node_or_null("Path").m()`, `self.m()`, `preload("res://x.gd").m()`
 static METHOD_CALL_RE: OnceLock<Regex> = OnceLock::new();
 static BRACKET_CALL_RE: OnceLock<Regex> = OnceLock::new();
+/// String value in a dict literal, `{State.IDLE: "idle_tick", ...}` (one entry per line allowed)
+static DICT_STRING_VALUE_RE: OnceLock<Regex> = OnceLock::new();
+/// Dynamic dispatch with a computed name: `call(states[current])`, `callv(name, args)`,
+/// `Callable(self, name)` – enables dict string values as references
+static DYNAMIC_CALL_RE: OnceLock<Regex> = OnceLock::new();
+/// Callable looked up by key, then called: `data.get("handler").call()`, `table["k"].callv(args)`
+static DYNAMIC_LOOKUP_CALL_RE: OnceLock<Regex> = OnceLock::new();
+/// `has_method("name")` – only a string-literal argument counts (dynamic dispatch usually follows)
+static HAS_METHOD_RE: OnceLock<Regex> = OnceLock::new();
+/// `get_tree().call_group("group", "method", ...)` / `call_group_flags(flags, "group", "method")`
+/// – the method name is the string after the group
+static CALL_GROUP_RE: OnceLock<Regex> = OnceLock::new();
+/// Bound callable: handler.bind(args) – handler is a function turned into a Callable
+static BIND_RE: OnceLock<Regex> = OnceLock::new();
 static ID_CALL_RE: OnceLock<Regex> = OnceLock::new();
 static NESTED_CALL_RE: OnceLock<Regex> = OnceLock::new();
+/// `= ident` with the identifier as a value: plain assignment, `:=`, and typed or annotated
+/// declarations (`@onready var cb := handler`, `@export var cb: Callable = handler`), at any
+/// indentation, including inside lambda bodies (`func(): var h = handler; h.call()`)
 static ASSIGN_RHS_RE: OnceLock<Regex> = OnceLock::new();
 /// Identifier as first argument of a call: foo(callback, ...) or foo(callback) — e.g. tween_method(set_master_volume, ...)
 static FIRST_ARG_IDENT_RE: OnceLock<Regex> = OnceLock::new();
+/// Comparator-taking Array methods: `sort_custom(_cmp)`, `bsearch_custom(value, _cmp)` – the
+/// callback may be any argument, not just the first
+static COMPARATOR_CALL_RE: OnceLock<Regex> = OnceLock::new();
+/// Godot 4 property accessors: `var x: int: get = _get_x, set = _set_x` (inline or in the
+/// indented `get = ...` / `set = ...` block under the declaration)
+static PROPERTY_ACCESSOR_RE: OnceLock<Regex> = OnceLock::new();
+/// Godot 3 `var x setget setter, getter` (either name may be omitted: `setget , getter`)
+static SETGET_RE: OnceLock<Regex> = OnceLock::new();
+
+/// @tool (Godot 4) or tool (Godot 3) at the start of a line – script runs in the editor
+static TOOL_RE: OnceLock<Regex> = OnceLock::new();
+/// ClassDB.class_call_static(class, "method", ...) – method name is the second argument
+static CLASSDB_CALL_RE: OnceLock<Regex> = OnceLock::new();
+/// callv("method", args), call_thread_safe("method"), call_deferred_thread_group("method")
+static STRING_DISPATCH_RE: OnceLock<Regex> = OnceLock::new();
+/// Godot 3 EditorPlugin: add_tool_menu_item("Label", handler, "callback")
+static TOOL_MENU_ITEM_RE: OnceLock<Regex> = OnceLock::new();
+
+/// const Alias = ClassName | const Alias = preload("path") (optional `: Type`, trailing comment allowed)
+static CONST_ALIAS_RE: OnceLock<Regex> = OnceLock::new();
+
+/// extends ClassName | extends "res://base.gd"
+static EXTENDS_RE: OnceLock<Regex> = OnceLock::new();
+/// preload("res://x.gd") / load("res://x.gd")
+static LOAD_SCRIPT_RE: OnceLock<Regex> = OnceLock::new();
+/// Capitalized identifier (candidate class_name reference)
+static CLASS_TOKEN_RE: OnceLock<Regex> = OnceLock::new();
+/// `Class.method` as a value (no call parens) after `=`, `(` or `,`: `var f = Factory.build`,
+/// `run(Factory.build)`
+static STATIC_METHOD_VALUE_RE: OnceLock<Regex> = OnceLock::new();
+/// Method call on a capitalized identifier: `AudioManager.play_sfx(` (autoload singletons)
+static RECEIVER_CALL_RE: OnceLock<Regex> = OnceLock::new();
+/// `self.method` / `obj.method` as a value (no call parens) after `=`, `(`, `[`, `,` or `return`:
+/// `var cbs = [self._step]`, `return player.on_hit`
+static MEMBER_VALUE_RE: OnceLock<Regex> = OnceLock::new();
 
 static KEYWORDS: OnceLock<std::collections::HashSet<&'static str>> = OnceLock::new();
 
@@ -36,41 +95,90 @@ fn keywords() -> &'static std::collections::HashSet<&'static str> {
     })
 }
 
+/// Reference to `name` found at byte offset `pos` of `source`.
+fn reference(source: &str, name: &str, pos: usize, kind: RefKind) -> Reference {
+    let (line, column) = line_col(source, pos);
+    Reference {
+        name: name.to_string(),
+        line,
+        column,
+        kind,
+        confidence: Confidence::Exact,
+    }
+}
+
+/// Like [`reference`], for broad patterns that may match unrelated identifiers.
+fn heuristic(source: &str, name: &str, pos: usize, kind: RefKind) -> Reference {
+    Reference {
+        confidence: Confidence::Heuristic,
+        ..reference(source, name, pos, kind)
+    }
+}
+
 /// Find references to functions in source (calls, connect(callback), call("name"), etc.).
 /// Returns list of (function_name, line_number).
 /// Source should be normalized (see util.normalize_source) for consistent line endings.
-pub fn find_function_references(_path: &Path, source: &str) -> Vec<(String, u32)> {
+/// `#` comments are blanked first, so commented-out calls do not count.
+pub fn find_function_references(path: &Path, source: &str) -> Vec<(String, u32)> {
+    find_references(path, source)
+        .into_iter()
+        .map(|r| (r.name, r.line))
+        .collect()
+}
+
+/// Like [`find_function_references`], dropping loose matches (identifiers assigned or passed as
+/// values, dict string values) that name none of the `known` functions; calls, connects and
+/// by-name references are kept whatever their name.
+pub fn find_function_references_known(
+    path: &Path,
+    source: &str,
+    known: &HashSet<String>,
+) -> Vec<(String, u32)> {
+    let mut refs = find_references(path, source);
+    retain_known_references(&mut refs, known);
+    refs.into_iter().map(|r| (r.name, r.line)).collect()
+}
+
+/// Drop heuristic-confidence references whose name is not in `known`; exact ones are kept.
+pub fn retain_known_references(refs: &mut Vec<Reference>, known: &HashSet<String>) {
+    refs.retain(|r| r.confidence == Confidence::Exact || known.contains(&r.name));
+}
+
+/// Like [`find_function_references`], also reporting whether each reference is a bare identifier
+/// (resolves in the caller's own scope) or qualified / by name (may target any script).
+pub fn find_references(_path: &Path, source: &str) -> Vec<Reference> {
+    let source = &strip_comments(source);
     let stripped = strip_string_literals(source);
-    let mut refs: Vec<(String, u32)> = Vec::new();
-    let line_at = |pos: usize| -> u32 { (source[..pos].matches('\n').count() + 1) as u32 };
+    let mut refs: Vec<Reference> = Vec::new();
+    let kw = keywords();
 
     // 1. call("func_name") or call_deferred("func_name") – string is the name (use source)
     let re = CALL_DOT_RE
         .get_or_init(|| Regex::new(r#"\.call\s*\(\s*["']([a-zA-Z_][a-zA-Z0-9_]*)["']"#).unwrap());
     for cap in re.captures_iter(source) {
         let m = cap.get(1).unwrap();
-        refs.push((m.as_str().to_string(), line_at(m.start())));
+        refs.push(reference(source, m.as_str(), m.start(), RefKind::Qualified));
     }
     let re = CALL_STANDALONE_RE.get_or_init(|| {
         Regex::new(r#"(?:^|\n|[^a-zA-Z0-9.])call\s*\(\s*["']([a-zA-Z_][a-zA-Z0-9_]*)["']"#).unwrap()
     });
     for cap in re.captures_iter(source) {
         let m = cap.get(1).unwrap();
-        refs.push((m.as_str().to_string(), line_at(m.start())));
+        refs.push(reference(source, m.as_str(), m.start(), RefKind::Bare));
     }
     let re = CALL_DEFERRED_RE.get_or_init(|| {
         Regex::new(r#"call_deferred\s*\(\s*["']([a-zA-Z_][a-zA-Z0-9_]*)["']"#).unwrap()
     });
     for cap in re.captures_iter(source) {
         let m = cap.get(1).unwrap();
-        refs.push((m.as_str().to_string(), line_at(m.start())));
+        refs.push(reference(source, m.as_str(), m.start(), RefKind::Qualified));
     }
     let re = CALL_DOT_DEFERRED_RE.get_or_init(|| {
         Regex::new(r#"\.call_deferred\s*\(\s*["']([a-zA-Z_][a-zA-Z0-9_]*)["']"#).unwrap()
     });
     for cap in re.captures_iter(source) {
         let m = cap.get(1).unwrap();
-        refs.push((m.as_str().to_string(), line_at(m.start())));
+        refs.push(reference(source, m.as_str(), m.start(), RefKind::Qualified));
     }
 
     // 1b. Callable(self, "func_name") or Callable(obj, "func_name")
@@ -82,7 +190,7 @@ pub fn find_function_references(_path: &Path, source: &str) -> Vec<(String, u32)
     });
     for cap in re.captures_iter(source) {
         let m = cap.get(1).unwrap();
-        refs.push((m.as_str().to_string(), line_at(m.start())));
+        refs.push(reference(source, m.as_str(), m.start(), RefKind::Qualified));
     }
 
     // 2. .connect(some_func) or .connect(self.some_func)
@@ -91,7 +199,20 @@ pub fn find_function_references(_path: &Path, source: &str) -> Vec<(String, u32)
     });
     for cap in re.captures_iter(&stripped) {
         let m = cap.get(0).unwrap();
-        refs.push((cap.get(1).unwrap().as_str().to_string(), line_at(m.start())));
+        let name = cap.get(1).unwrap().as_str();
+        refs.push(reference(source, name, m.start(), RefKind::Bare));
+    }
+
+    // 2a. connect("signal", target, "method") – legacy form, method name is a string (use source)
+    let re = CONNECT_LEGACY_RE.get_or_init(|| {
+        Regex::new(
+            r#"(?:^|[^a-zA-Z0-9_])connect\s*\(\s*["'][^"'\n]*["']\s*,\s*[^,()\n]+?\s*,\s*["']([a-zA-Z_][a-zA-Z0-9_]*)["']"#,
+        )
+        .unwrap()
+    });
+    for cap in re.captures_iter(source) {
+        let m = cap.get(1).unwrap();
+        refs.push(reference(source, m.as_str(), m.start(), RefKind::Qualified));
     }
 
     // 2b. obj.method_name( – explicit method call (stripped only to avoid refs inside strings)
@@ -99,15 +220,71 @@ pub fn find_function_references(_path: &Path, source: &str) -> Vec<(String, u32)
         METHOD_CALL_RE.get_or_init(|| Regex::new(r"\.\s*([a-zA-Z_][a-zA-Z0-9_]*)\s*\(").unwrap());
     for cap in re.captures_iter(&stripped) {
         let m = cap.get(1).unwrap();
-        refs.push((m.as_str().to_string(), line_at(m.start())));
+        refs.push(reference(source, m.as_str(), m.start(), RefKind::Qualified));
     }
 
-    // 2c. obj["method_name"]( or obj['method_name']( – dynamic method call
+    // 2c. obj["method_name"]( or obj['method_name']( – dynamic method call (use source)
     let re = BRACKET_CALL_RE
         .get_or_init(|| Regex::new(r#"\[\s*["']([a-zA-Z_][a-zA-Z0-9_]*)["']\s*\]\s*\("#).unwrap());
+    for cap in re.captures_iter(source) {
+        let m = cap.get(1).unwrap();
+        refs.push(reference(source, m.as_str(), m.start(), RefKind::Qualified));
+    }
+
+    // 2d. handler.bind(...) – function used as a bound Callable
+    let re =
+        BIND_RE.get_or_init(|| Regex::new(r"([a-zA-Z_][a-zA-Z0-9_]*)\s*\.\s*bind\s*\(").unwrap());
     for cap in re.captures_iter(&stripped) {
         let m = cap.get(1).unwrap();
-        refs.push((m.as_str().to_string(), line_at(m.start())));
+        if !kw.contains(m.as_str()) {
+            let qualified = stripped[..m.start()].trim_end().ends_with('.');
+            let kind = if qualified {
+                RefKind::Qualified
+            } else {
+                RefKind::Bare
+            };
+            refs.push(reference(source, m.as_str(), m.start(), kind));
+        }
+    }
+
+    // 2e. obj.has_method("method_name") – string is the name (use source)
+    let re = HAS_METHOD_RE.get_or_init(|| {
+        Regex::new(r#"\bhas_method\s*\(\s*&?["']([a-zA-Z_][a-zA-Z0-9_]*)["']\s*\)"#).unwrap()
+    });
+    for cap in re.captures_iter(source) {
+        let m = cap.get(1).unwrap();
+        refs.push(reference(source, m.as_str(), m.start(), RefKind::Qualified));
+    }
+
+    // 2f. {State.IDLE: "idle_tick"} – dict string values are method names when the script
+    // dispatches dynamically (`call(states[current])`); values of other dicts are left alone
+    let dynamic_re = DYNAMIC_CALL_RE.get_or_init(|| {
+        Regex::new(r#"\b(?:(?:call|call_deferred|callv)\s*\(|Callable\s*\(\s*[^,()]+,)\s*[^"'\s)]"#)
+            .unwrap()
+    });
+    if dynamic_re.is_match(&stripped) {
+        let re = DICT_STRING_VALUE_RE.get_or_init(|| {
+            Regex::new(
+                r#"(?m)(?:^|[{,])[ \t]*[^:{},\n]+?[ \t]*:[ \t]*["']([a-zA-Z_][a-zA-Z0-9_]*)["'][ \t]*(?:[,}]|$)"#,
+            )
+            .unwrap()
+        });
+        for cap in re.captures_iter(source) {
+            let m = cap.get(1).unwrap();
+            refs.push(heuristic(source, m.as_str(), m.start(), RefKind::Qualified));
+        }
+    }
+
+    // 2g. call_group("group", "method") / call_group_flags(flags, "group", "method") (use source)
+    let re = CALL_GROUP_RE.get_or_init(|| {
+        Regex::new(
+            r#"\bcall_group(?:_flags\s*\(\s*[^,\n]+?\s*,|\s*\()\s*[^,\n]+?\s*,\s*&?["']([a-zA-Z_][a-zA-Z0-9_]*)["']"#,
+        )
+        .unwrap()
+    });
+    for cap in re.captures_iter(source) {
+        let m = cap.get(1).unwrap();
+        refs.push(reference(source, m.as_str(), m.start(), RefKind::Qualified));
     }
 
     // 3. identifier( – direct call; 3b. ( identifier ( – nested call (stripped only to avoid refs inside strings)
@@ -116,17 +293,26 @@ pub fn find_function_references(_path: &Path, source: &str) -> Vec<(String, u32)
     });
     let nested_re =
         NESTED_CALL_RE.get_or_init(|| Regex::new(r"\(\s*([a-zA-Z_][a-zA-Z0-9_]*)\s*\(").unwrap());
-    let kw = keywords();
     for cap in id_re.captures_iter(&stripped) {
         let name = cap.get(1).unwrap().as_str();
         if !kw.contains(name) {
-            refs.push((name.to_string(), line_at(cap.get(1).unwrap().start())));
+            refs.push(reference(
+                source,
+                name,
+                cap.get(1).unwrap().start(),
+                RefKind::Bare,
+            ));
         }
     }
     for cap in nested_re.captures_iter(&stripped) {
         let name = cap.get(1).unwrap().as_str();
         if !kw.contains(name) {
-            refs.push((name.to_string(), line_at(cap.get(1).unwrap().start())));
+            refs.push(reference(
+                source,
+                name,
+                cap.get(1).unwrap().start(),
+                RefKind::Bare,
+            ));
         }
     }
 
@@ -139,7 +325,12 @@ pub fn find_function_references(_path: &Path, source: &str) -> Vec<(String, u32)
         if next == "(" || kw.contains(name) {
             continue;
         }
-        refs.push((name.to_string(), line_at(cap.get(1).unwrap().start())));
+        refs.push(heuristic(
+            source,
+            name,
+            cap.get(1).unwrap().start(),
+            RefKind::Bare,
+        ));
     }
 
     // 5. identifier as first argument of a call: tween_method(set_master_volume, from, to, duration)
@@ -148,9 +339,239 @@ pub fn find_function_references(_path: &Path, source: &str) -> Vec<(String, u32)
     for cap in re.captures_iter(&stripped) {
         let name = cap.get(1).unwrap().as_str();
         if !kw.contains(name) {
-            refs.push((name.to_string(), line_at(cap.get(1).unwrap().start())));
+            refs.push(heuristic(
+                source,
+                name,
+                cap.get(1).unwrap().start(),
+                RefKind::Bare,
+            ));
+        }
+    }
+
+    // 5b. sort_custom(_cmp) / bsearch_custom(x, _cmp) – every bare identifier argument
+    let re = COMPARATOR_CALL_RE
+        .get_or_init(|| Regex::new(r"\b(?:sort_custom|bsearch_custom)\s*\(([^()\n]*)\)").unwrap());
+    for cap in re.captures_iter(&stripped) {
+        let args = cap.get(1).unwrap();
+        let mut offset = args.start();
+        for arg in args.as_str().split(',') {
+            let name = arg.trim();
+            let is_ident = name
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if is_ident && !kw.contains(name) {
+                let start = offset + (arg.len() - arg.trim_start().len());
+                refs.push(reference(source, name, start, RefKind::Bare));
+            }
+            offset += arg.len() + 1;
+        }
+    }
+
+    // 6. get = _get_x / set = _set_x – property accessor functions
+    let re = PROPERTY_ACCESSOR_RE.get_or_init(|| {
+        Regex::new(r"(?:^|[\s:,])(?:get|set)\s*=\s*([a-zA-Z_][a-zA-Z0-9_]*)").unwrap()
+    });
+    for cap in re.captures_iter(&stripped) {
+        let m = cap.get(1).unwrap();
+        refs.push(reference(source, m.as_str(), m.start(), RefKind::Bare));
+    }
+
+    // 6b. setget setter, getter – Godot 3 property accessors
+    let re = SETGET_RE.get_or_init(|| {
+        Regex::new(r"\bsetget\s+([a-zA-Z_][a-zA-Z0-9_]*)?(?:\s*,\s*([a-zA-Z_][a-zA-Z0-9_]*))?")
+            .unwrap()
+    });
+    for cap in re.captures_iter(&stripped) {
+        for m in [cap.get(1), cap.get(2)].into_iter().flatten() {
+            refs.push(reference(source, m.as_str(), m.start(), RefKind::Bare));
         }
     }
 
+    if is_tool_script(source) {
+        refs.extend(find_tool_string_references(source));
+    }
+
     refs
 }
+
+/// True if the script is annotated `@tool` (or Godot 3 `tool`) and therefore runs in the editor.
+fn is_tool_script(source: &str) -> bool {
+    TOOL_RE
+        .get_or_init(|| Regex::new(r"(?m)^\s*@?tool\b").unwrap())
+        .is_match(source)
+}
+
+/// String-dispatched method names used by editor tool scripts (ClassDB / EditorPlugin APIs).
+fn find_tool_string_references(source: &str) -> Vec<Reference> {
+    let classdb = CLASSDB_CALL_RE.get_or_init(|| {
+        Regex::new(
+            r#"ClassDB\s*\.\s*class_call_static\s*\(\s*[^,\n]+?\s*,\s*["']([a-zA-Z_][a-zA-Z0-9_]*)["']"#,
+        )
+        .unwrap()
+    });
+    let dispatch = STRING_DISPATCH_RE.get_or_init(|| {
+        Regex::new(
+            r#"(?:callv|call_thread_safe|call_deferred_thread_group)\s*\(\s*["']([a-zA-Z_][a-zA-Z0-9_]*)["']"#,
+        )
+        .unwrap()
+    });
+    let menu_item = TOOL_MENU_ITEM_RE.get_or_init(|| {
+        Regex::new(
+            r#"add_tool_menu_item\s*\(\s*[^,\n]+?\s*,\s*[^,\n]+?\s*,\s*["']([a-zA-Z_][a-zA-Z0-9_]*)["']"#,
+        )
+        .unwrap()
+    });
+    [classdb, dispatch, menu_item]
+        .iter()
+        .flat_map(|re| re.captures_iter(source))
+        .map(|cap| {
+            let m = cap.get(1).unwrap();
+            reference(source, m.as_str(), m.start(), RefKind::Qualified)
+        })
+        .collect()
+}
+
+/// Find `const Alias = ClassName` and `const Alias = preload("...")` type aliases.
+/// Returns (alias, target); preload paths are returned as written (e.g. `res://weapon.gd`).
+pub fn find_const_aliases(_path: &Path, source: &str) -> Vec<(String, AliasTarget)> {
+    let re = CONST_ALIAS_RE.get_or_init(|| {
+        Regex::new(
+            r#"(?m)^[ \t]*const[ \t]+([a-zA-Z_][a-zA-Z0-9_]*)[ \t]*(?::[ \t]*[a-zA-Z_][a-zA-Z0-9_]*[ \t]*)?=[ \t]*(?:preload[ \t]*\([ \t]*["']([^"'\n]+)["'][ \t]*\)|([A-Z][a-zA-Z0-9_]*))[ \t]*(?:#.*)?$"#,
+        )
+        .unwrap()
+    });
+    re.captures_iter(source)
+        .map(|cap| {
+            let alias = cap[1].to_string();
+            let target = match cap.get(2) {
+                Some(path) => AliasTarget::Script(path.as_str().into()),
+                None => AliasTarget::Class(cap[3].to_string()),
+            };
+            (alias, target)
+        })
+        .collect()
+}
+
+/// Find calls of a Callable fetched by key (`data.get("handler").call()`, `table["k"].call()`):
+/// the key names an entry, not a function, so nothing is credited. Returns (line, call text) for
+/// `--warn-dynamic`.
+pub fn find_dynamic_calls(_path: &Path, source: &str) -> Vec<(u32, String)> {
+    let source = &strip_comments(source);
+    let re = DYNAMIC_LOOKUP_CALL_RE.get_or_init(|| {
+        Regex::new(
+            r#"(?:\.\s*get\s*\(\s*["'][^"'\n]*["']\s*\)|\[\s*["'][^"'\n]*["']\s*\])\s*\.\s*(?:call|callv|call_deferred)\s*\("#,
+        )
+        .unwrap()
+    });
+    re.find_iter(source)
+        .map(|m| (line_col(source, m.start()).0, m.as_str().to_string()))
+        .collect()
+}
+
+/// Find `Class.method` used as a value without calling it (`var f = Factory.build`), e.g. a static
+/// method stored as a Callable. Returns (class identifier, reference to the method); the scan
+/// keeps only those whose class resolves to a script defining `static func method`.
+pub fn find_static_method_values(_path: &Path, source: &str) -> Vec<(String, Reference)> {
+    let source = &strip_comments(source);
+    let stripped = strip_string_literals(source);
+    let re = STATIC_METHOD_VALUE_RE.get_or_init(|| {
+        Regex::new(
+            r"(?m)[=(,][ \t]*([A-Z][a-zA-Z0-9_]*)[ \t]*\.[ \t]*([a-zA-Z_][a-zA-Z0-9_]*)[ \t]*(?:[,)\]}]|$)",
+        )
+        .unwrap()
+    });
+    re.captures_iter(&stripped)
+        .map(|cap| {
+            let m = cap.get(2).unwrap();
+            let r = reference(source, m.as_str(), m.start(), RefKind::Qualified);
+            (cap[1].to_string(), r)
+        })
+        .collect()
+}
+
+/// Find `self.member` / `obj.member` used as a value without calling it (`var cbs = [self._step]`),
+/// e.g. a method stored as a Callable. Capitalized receivers are left to
+/// [`find_static_method_values`]. Members are often plain properties, so the matches are
+/// heuristic and the scan keeps only names some script defines as a function (see
+/// [`retain_known_references`]).
+pub fn find_member_values(_path: &Path, source: &str) -> Vec<Reference> {
+    let source = &strip_comments(source);
+    let stripped = strip_string_literals(source);
+    let re = MEMBER_VALUE_RE.get_or_init(|| {
+        Regex::new(
+            r"(?m)(?:[=(\[,]|\breturn\b)[ \t]*(self|[a-z_][a-zA-Z0-9_]*)[ \t]*\.[ \t]*([a-zA-Z_][a-zA-Z0-9_]*)[ \t]*(?:[,)\]}]|$)",
+        )
+        .unwrap()
+    });
+    re.captures_iter(&stripped)
+        .map(|cap| {
+            let m = cap.get(2).unwrap();
+            let kind = if &cap[1] == "self" {
+                RefKind::Bare
+            } else {
+                RefKind::Qualified
+            };
+            heuristic(source, m.as_str(), m.start(), kind)
+        })
+        .collect()
+}
+
+/// Find method calls on a capitalized identifier (`AudioManager.play_sfx("x")`), e.g. an autoload
+/// singleton. Returns (receiver, reference to the method) for the same sites `find_references`
+/// reports as qualified calls; a receiver that is itself a member (`a.Audio.play()`) is skipped.
+pub fn find_receiver_calls(_path: &Path, source: &str) -> Vec<(String, Reference)> {
+    let source = &strip_comments(source);
+    let stripped = strip_string_literals(source);
+    let re = RECEIVER_CALL_RE.get_or_init(|| {
+        Regex::new(r"\b([A-Z][a-zA-Z0-9_]*)[ \t]*\.[ \t]*([a-zA-Z_][a-zA-Z0-9_]*)\s*\(").unwrap()
+    });
+    re.captures_iter(&stripped)
+        .filter(|cap| {
+            let start = cap.get(1).unwrap().start();
+            !stripped[..start]
+                .trim_end_matches([' ', '\t'])
+                .ends_with('.')
+        })
+        .map(|cap| {
+            let m = cap.get(2).unwrap();
+            let r = reference(source, m.as_str(), m.start(), RefKind::Qualified);
+            (cap[1].to_string(), r)
+        })
+        .collect()
+}
+
+/// Find the scripts a file depends on: its `extends` target, scripts it preloads/loads and the
+/// capitalized identifiers it uses (matched against `class_name`s after the scan).
+/// Script paths are returned as written (e.g. `res://base.gd`).
+pub fn find_script_refs(_path: &Path, source: &str) -> ScriptRefs {
+    let source = &strip_comments(source);
+    let stripped = strip_string_literals(source);
+    let extends_re = EXTENDS_RE.get_or_init(|| {
+        Regex::new(r#"(?m)^[ \t]*extends[ \t]+(?:["']([^"'\n]+)["']|([a-zA-Z_][a-zA-Z0-9_]*))"#)
+            .unwrap()
+    });
+    let load_re = LOAD_SCRIPT_RE
+        .get_or_init(|| Regex::new(r#"\b(?:preload|load)\s*\(\s*["']([^"'\n]+\.gd)["']"#).unwrap());
+    let token_re = CLASS_TOKEN_RE.get_or_init(|| Regex::new(r"\b([A-Z][a-zA-Z0-9_]*)\b").unwrap());
+    let extends = extends_re.captures(source).map(|cap| match cap.get(1) {
+        Some(path) => AliasTarget::Script(path.as_str().into()),
+        None => AliasTarget::Class(cap[2].to_string()),
+    });
+    let mut scripts: std::collections::HashSet<_> = load_re
+        .captures_iter(source)
+        .map(|cap| std::path::PathBuf::from(&cap[1]))
+        .collect();
+    if let Some(AliasTarget::Script(path)) = &extends {
+        scripts.insert(path.clone());
+    }
+    ScriptRefs {
+        class_tokens: token_re
+            .captures_iter(&stripped)
+            .map(|cap| cap[1].to_string())
+            .collect(),
+        scripts,
+        extends,
+    }
+}
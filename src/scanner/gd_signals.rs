@@ -0,0 +1,108 @@
+//! Extract signal declarations and signal references (emit/connect) from GDScript source.
+
+use std::path::Path;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use super::gd_definitions::{line_has_ignore_marker, strip_comments};
+use super::models::SignalDef;
+
+/// `signal name` or `signal name(args)` at the start of a line.
+static SIGNAL_DEF_RE: OnceLock<Regex> = OnceLock::new();
+
+/// `name.emit(` (Godot 4), `emit_signal("name"` (Godot 3 / by name), `name.connect(` and
+/// `connect("name"` (Godot 3 `obj.connect("name", target, "method")`).
+static SIGNAL_EMIT_RE: OnceLock<Regex> = OnceLock::new();
+static EMIT_SIGNAL_RE: OnceLock<Regex> = OnceLock::new();
+static SIGNAL_CONNECT_RE: OnceLock<Regex> = OnceLock::new();
+static CONNECT_BY_NAME_RE: OnceLock<Regex> = OnceLock::new();
+
+fn signal_def_re() -> &'static Regex {
+    SIGNAL_DEF_RE.get_or_init(|| {
+        Regex::new(r"(?m)^[ \t]*signal[ \t]+([a-zA-Z_][a-zA-Z0-9_]*)[ \t]*(\(|:|;|#|$)").unwrap()
+    })
+}
+
+/// Extract all signal declarations from a GDScript source. Declarations tagged with an
+/// ignore marker (e.g. `# gdcf-ignore`) on the same line get `ignore_dead_code: true`.
+pub fn find_signal_definitions(path: &Path, source: &str) -> Vec<SignalDef> {
+    let masked = strip_comments(source);
+    let mut out = Vec::new();
+    for cap in signal_def_re().captures_iter(&masked) {
+        let m = cap.get(1).unwrap();
+        let line_start = source[..m.start()].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[m.end()..]
+            .find('\n')
+            .map_or(source.len(), |i| m.end() + i);
+        out.push(SignalDef {
+            name: m.as_str().to_string(),
+            file: path.to_path_buf(),
+            line: (source[..m.start()].matches('\n').count() + 1) as u32,
+            ignore_dead_code: line_has_ignore_marker(&source[line_start..line_end]),
+        });
+    }
+    out
+}
+
+/// Find signal references: `name.emit(...)`, `emit_signal("name", ...)`, `name.connect(...)`
+/// and `connect("name", ...)`. Returns (signal_name, line_number) pairs; comments are ignored.
+pub fn find_signal_references(_path: &Path, source: &str) -> Vec<(String, u32)> {
+    let source = strip_comments(source);
+    let emit = SIGNAL_EMIT_RE
+        .get_or_init(|| Regex::new(r"\b([a-zA-Z_][a-zA-Z0-9_]*)\s*\.\s*emit\s*\(").unwrap());
+    let emit_signal = EMIT_SIGNAL_RE.get_or_init(|| {
+        Regex::new(r#"\bemit_signal\s*\(\s*&?["']([a-zA-Z_][a-zA-Z0-9_]*)["']"#).unwrap()
+    });
+    let connect = SIGNAL_CONNECT_RE
+        .get_or_init(|| Regex::new(r"\b([a-zA-Z_][a-zA-Z0-9_]*)\s*\.\s*connect\s*\(").unwrap());
+    let connect_by_name = CONNECT_BY_NAME_RE.get_or_init(|| {
+        Regex::new(r#"\bconnect\s*\(\s*&?["']([a-zA-Z_][a-zA-Z0-9_]*)["']"#).unwrap()
+    });
+    let mut refs = Vec::new();
+    for re in [emit, emit_signal, connect, connect_by_name] {
+        for cap in re.captures_iter(&source) {
+            let m = cap.get(1).unwrap();
+            let line = (source[..m.start()].matches('\n').count() + 1) as u32;
+            refs.push((m.as_str().to_string(), line));
+        }
+    }
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_signal_definitions_with_and_without_args() {
+        let src = "extends Node\nsignal died\nsignal hit(damage: int)\n# signal old\nsignal kept # gdcf-ignore\n";
+        let defs = find_signal_definitions(Path::new("a.gd"), src);
+        let names: Vec<_> = defs.iter().map(|d| (d.name.as_str(), d.line)).collect();
+        assert_eq!(names, vec![("died", 2), ("hit", 3), ("kept", 5)]);
+        assert!(!defs[0].ignore_dead_code);
+        assert!(defs[2].ignore_dead_code);
+    }
+
+    #[test]
+    fn find_signal_references_emit_and_connect() {
+        let src = r#"func _ready():
+    hit.emit(3)
+    emit_signal("died")
+    other.moved.connect(_on_moved)
+    connect("legacy", self, "_on_legacy")
+    # ghost.emit()
+"#;
+        let mut refs = find_signal_references(Path::new("a.gd"), src);
+        refs.sort();
+        assert_eq!(
+            refs,
+            vec![
+                ("died".to_string(), 3),
+                ("hit".to_string(), 2),
+                ("legacy".to_string(), 5),
+                ("moved".to_string(), 4),
+            ]
+        );
+    }
+}
@@ -1,21 +1,50 @@
-//! Scan GDScript and .tscn files for function definitions and references.
+//! Scan GDScript, .tscn and .tres files for function definitions and references.
 
 mod analysis;
+mod cache;
 mod constants;
 mod files;
+pub mod gd_consts;
 pub mod gd_definitions;
+pub mod gd_enums;
 mod gd_references;
+mod gd_signals;
 mod models;
+pub mod project_godot;
 mod scan;
 mod tscn;
 pub mod util;
 
 pub use analysis::{
-    default_is_test_path, find_only_test_referenced_functions, find_unused_functions,
+    always_used_reason, analyze, analyze_scan, counted_references, default_is_test_path,
+    find_duplicate_definitions, find_only_test_referenced_functions,
+    find_only_test_referenced_functions_with, find_unreachable_functions, find_unused_consts,
+    find_unused_enum_members, find_unused_functions, find_unused_functions_with,
+    find_unused_signals, Analysis, AnalysisOptions, AnalyzeOptions, SharedTestPathFn,
+};
+pub(crate) use cache::{fnv1a, ScanCache};
+pub use constants::TestFramework;
+pub use files::{
+    is_glob_pattern, is_walked_path, iter_files_by_extension, iter_gd_files, iter_tres_files,
+    iter_tscn_files, IgnorePatterns, WalkOptions,
+};
+pub use gd_definitions::{find_class_name, find_function_definitions};
+pub use gd_references::{
+    find_const_aliases, find_dynamic_calls, find_function_references,
+    find_function_references_known, find_member_values, find_receiver_calls, find_references,
+    find_script_refs, find_static_method_values, retain_known_references,
+};
+pub use gd_signals::{find_signal_definitions, find_signal_references};
+pub use models::{
+    AliasTarget, Confidence, ConstDef, EnumMemberDef, FunctionDef, RefKind, RefSite, Reference,
+    ScanResult, ScriptRefs, SignalDef,
+};
+pub(crate) use scan::SourceLines;
+pub use scan::{
+    gdscript_suffixes, scan_directory, scan_directory_with_options, scan_directory_with_progress,
+    ScanOptions, DEFAULT_NOREF_PATTERN,
+};
+pub use tscn::{
+    find_tres_references, find_tscn_property_references, find_tscn_references,
+    find_tscn_signal_references,
 };
-pub use files::{iter_gd_files, iter_tscn_files};
-pub use gd_definitions::find_function_definitions;
-pub use gd_references::find_function_references;
-pub use models::{FunctionDef, ScanResult};
-pub use scan::scan_directory;
-pub use tscn::find_tscn_references;
@@ -2,24 +2,160 @@
 
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
 
 /// A function definition in a GDScript file.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FunctionDef {
     pub name: String,
     pub file: PathBuf,
     pub line: u32,
+    /// 1-based column (in chars) of the function name.
+    pub column: u32,
+    /// Last line of the function body (equal to `line` for a one-line function).
+    pub end_line: u32,
     pub is_static: bool,
+    /// Annotated `@rpc` / `@rpc(...)`: invoked remotely, so never reported as unused.
+    pub is_rpc: bool,
     /// If true, do not report as unused (tagged with e.g. # gdcf-ignore or # TODO: dead-code).
     pub ignore_dead_code: bool,
+    /// Enclosing inner class (`class Name:`), dotted when nested (`Outer.Inner`); `None` for a
+    /// function at file scope.
+    #[serde(default)]
+    pub inner_class: Option<String>,
+}
+
+impl FunctionDef {
+    /// Lines spanned by the definition, `func` line and body (what deleting it would remove).
+    pub fn line_count(&self) -> u32 {
+        self.end_line.saturating_sub(self.line) + 1
+    }
+}
+
+/// A `signal` declaration in a GDScript file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignalDef {
+    pub name: String,
+    pub file: PathBuf,
+    pub line: u32,
+    /// If true, do not report as unused (tagged with e.g. # gdcf-ignore).
+    pub ignore_dead_code: bool,
+}
+
+/// A `const` declaration in a GDScript file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConstDef {
+    pub name: String,
+    pub file: PathBuf,
+    pub line: u32,
+    /// If true, do not report as unused (tagged with e.g. # gdcf-ignore).
+    pub ignore_dead_code: bool,
+}
+
+/// A member of a named (`enum State { IDLE }`) or anonymous (`enum { IDLE }`) enum.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnumMemberDef {
+    /// Member name (`IDLE`).
+    pub name: String,
+    /// Name of the enclosing enum; `None` for an anonymous enum.
+    pub enum_name: Option<String>,
+    pub file: PathBuf,
+    pub line: u32,
+    /// If true, do not report as unused (tagged with e.g. # gdcf-ignore).
+    pub ignore_dead_code: bool,
+}
+
+impl EnumMemberDef {
+    /// `State.IDLE` for a named enum, bare `IDLE` for an anonymous one.
+    pub fn qualified_name(&self) -> String {
+        match &self.enum_name {
+            Some(enum_name) => format!("{}.{}", enum_name, self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// How a reference names its target function.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RefKind {
+    /// Unqualified identifier (`helper()`, `= helper`, `f(helper)`): resolves in the caller's own
+    /// script or one it inherits from / references.
+    Bare,
+    /// Member call, string name or scene connection (`obj.helper()`, `call("helper")`): may target
+    /// any script.
+    #[default]
+    Qualified,
 }
 
-/// A reference site (file path and line number).
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// How certain it is that a reference names a function (ordered: `Heuristic < Exact`).
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Confidence {
+    /// Broad pattern that may match unrelated identifiers: an identifier passed as a call
+    /// argument (`tween_method(handler, ...)`) or used as an assigned value (`= handler`).
+    Heuristic,
+    /// Direct call, `connect`, string dispatch, accessor clause or scene/resource connection.
+    Exact,
+}
+
+/// A reference found in one file (function name, line and how it was referenced).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reference {
+    pub name: String,
+    pub line: u32,
+    /// 1-based column (in chars) of the referenced name; 0 when not tracked.
+    pub column: u32,
+    pub kind: RefKind,
+    pub confidence: Confidence,
+}
+
+/// A reference site (file path, line and column).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct RefSite {
     pub path: PathBuf,
     pub line: u32,
+    /// 1-based column (in chars) of the referenced name in .gd files; 0 when not tracked
+    /// (.tscn/.tres connections, signal references).
+    pub column: u32,
+    pub kind: RefKind,
+    pub confidence: Confidence,
+    /// Script the call is known to go to (`AudioManager.play()` on an autoload singleton); it
+    /// only counts for definitions in that script or its `extends` ancestors. `None`: any script.
+    pub target: Option<PathBuf>,
+}
+
+/// Other scripts a file depends on: used to decide whether a bare call may target them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScriptRefs {
+    /// Capitalized identifiers used in code (candidate `class_name` references).
+    pub class_tokens: HashSet<String>,
+    /// Scripts named by `preload`/`load` (resolved to file paths by the scan).
+    pub scripts: HashSet<PathBuf>,
+    /// `extends` target, if any.
+    pub extends: Option<AliasTarget>,
+}
+
+/// Target of a `const Alias = ...` type alias.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AliasTarget {
+    /// `const W = Weapon` – a global `class_name`.
+    Class(String),
+    /// `const W = preload("res://weapon.gd")` – a script path (resolved to a file path by the scan).
+    Script(PathBuf),
 }
 
 /// Result of scanning a codebase.
@@ -27,27 +163,114 @@ pub struct RefSite {
 pub struct ScanResult {
     pub definitions: Vec<FunctionDef>,
     pub references: HashMap<String, std::collections::HashSet<RefSite>>,
+    /// `class_name` declarations: class name -> file declaring it.
+    pub class_names: HashMap<String, PathBuf>,
+    /// `const Alias = ...` type aliases per file: alias -> target.
+    pub const_aliases: HashMap<PathBuf, HashMap<String, AliasTarget>>,
+    /// Script dependencies per .gd file (see [`ScanResult::references_script`]).
+    pub script_refs: HashMap<PathBuf, ScriptRefs>,
+    /// `signal` declarations from .gd files.
+    pub signals: Vec<SignalDef>,
+    /// Signal references (`emit`, `emit_signal`, `connect`, .tscn `signal="..."`): name -> sites.
+    pub signal_references: HashMap<String, HashSet<RefSite>>,
+    /// `const` declarations from .gd files (collected with `ScanOptions::consts`).
+    pub consts: Vec<ConstDef>,
+    /// Enum members from .gd files (collected with `ScanOptions::enums`).
+    pub enum_members: Vec<EnumMemberDef>,
+    /// Identifier tokens in .gd files, any of which may name a constant or enum member:
+    /// name -> sites (collected with `ScanOptions::consts` or `ScanOptions::enums`).
+    pub token_references: HashMap<String, HashSet<RefSite>>,
+    /// Calls of Callables looked up by key (`data.get("handler").call()`), whose target is
+    /// unknown: (file, line, call text), sorted.
+    pub dynamic_calls: Vec<(PathBuf, u32, String)>,
+    /// Autoload singletons from project.godot: global name -> script (collected with
+    /// `ScanOptions::autoloads`).
+    pub autoloads: HashMap<String, PathBuf>,
+    /// Number of files parsed in this scan (files served from the cache are not counted).
+    pub parsed_files: usize,
+    /// Files found by the walk that could not be read (e.g. permission denied), sorted; they
+    /// contribute no definitions or references.
+    pub skipped_files: Vec<PathBuf>,
 }
 
 impl ScanResult {
+    /// Record an exact, qualified reference (one that may target any script); the column is not
+    /// tracked.
     pub fn add_reference(&mut self, name: String, path: PathBuf, line: u32) {
-        self.references
-            .entry(name)
-            .or_default()
-            .insert(RefSite { path, line });
+        let site = RefSite {
+            path,
+            line,
+            column: 0,
+            kind: RefKind::Qualified,
+            confidence: Confidence::Exact,
+            target: None,
+        };
+        self.add_ref_site(name, site);
     }
 
-    /// Set of (canonical path, line, name) for each definition site. Used to exclude def line from refs.
+    pub fn add_ref_site(&mut self, name: String, site: RefSite) {
+        self.references.entry(name).or_default().insert(site);
+    }
+
+    /// Script named by an `extends` or alias target, if known.
+    fn resolve_target<'a>(&'a self, target: &'a AliasTarget) -> Option<&'a Path> {
+        match target {
+            AliasTarget::Class(name) => self.class_names.get(name).map(|p| p.as_path()),
+            AliasTarget::Script(path) => Some(path.as_path()),
+        }
+    }
+
+    /// True if `to` is `from` itself or one of its `extends` ancestors.
+    pub fn inherits_from(&self, from: &Path, to: &Path) -> bool {
+        let mut current = Some(from);
+        let mut visited = HashSet::new();
+        while let Some(file) = current {
+            if file == to {
+                return true;
+            }
+            if !visited.insert(file) {
+                break;
+            }
+            current = self
+                .script_refs
+                .get(file)
+                .and_then(|r| r.extends.as_ref())
+                .and_then(|t| self.resolve_target(t));
+        }
+        false
+    }
+
+    /// True if code in `from` may call into `to` with a bare identifier: `to` is `from` itself or
+    /// one of its `extends` ancestors, or `from` names `to` via its `class_name` or a preload/load.
+    pub fn references_script(&self, from: &Path, to: &Path) -> bool {
+        if self.inherits_from(from, to) {
+            return true;
+        }
+        let Some(refs) = self.script_refs.get(from) else {
+            return false;
+        };
+        refs.scripts.contains(to)
+            || self
+                .class_names
+                .iter()
+                .any(|(name, file)| file == to && refs.class_tokens.contains(name))
+    }
+
+    /// Resolve a class-like identifier used in `file` to the script it names: a `const` alias
+    /// declared in that file (to a known `class_name` or a preloaded script), or a global `class_name`.
+    pub fn resolve_class(&self, file: &Path, ident: &str) -> Option<&Path> {
+        match self.const_aliases.get(file).and_then(|a| a.get(ident)) {
+            Some(target) => self.resolve_target(target),
+            None => self.class_names.get(ident).map(|p| p.as_path()),
+        }
+    }
+
+    /// Set of (path, line, name) for each definition site. Used to exclude def line from refs.
+    /// Scanned paths are already canonical, so sites compare without touching the filesystem.
     pub fn def_sites(&self) -> HashSet<(PathBuf, u32, String)> {
         self.definitions
             .iter()
-            .map(|fd| {
-                (
-                    fd.file.canonicalize().unwrap_or(fd.file.clone()),
-                    fd.line,
-                    fd.name.clone(),
-                )
-            })
+            .map(|fd| (fd.file.clone(), fd.line, fd.name.clone()))
             .collect()
     }
 
@@ -61,13 +284,26 @@ impl ScanResult {
             .get(name)
             .map(|s| {
                 s.iter()
-                    .filter(|r| {
-                        let path = r.path.canonicalize().unwrap_or(r.path.clone());
-                        !def_sites.contains(&(path, r.line, name.to_string()))
-                    })
+                    .filter(|r| !def_sites.contains(&(r.path.clone(), r.line, name.to_string())))
                     .cloned()
                     .collect()
             })
             .unwrap_or_default()
     }
+
+    /// Refs that may reach definition `fd`: [`Self::refs_excluding_def_sites`] without calls
+    /// known to target another script (see [`RefSite::target`]).
+    pub fn refs_for(
+        &self,
+        fd: &FunctionDef,
+        def_sites: &HashSet<(PathBuf, u32, String)>,
+    ) -> Vec<RefSite> {
+        let mut refs = self.refs_excluding_def_sites(&fd.name, def_sites);
+        refs.retain(|r| {
+            r.target
+                .as_deref()
+                .is_none_or(|target| self.inherits_from(target, &fd.file))
+        });
+        refs
+    }
 }
@@ -0,0 +1,194 @@
+//! Read settings from a Godot `project.godot` file.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+/// Project settings file at the root of a Godot project.
+pub const PROJECT_FILE: &str = "project.godot";
+
+/// `[section]` header at the start of a line.
+static SECTION_RE: OnceLock<Regex> = OnceLock::new();
+/// `key=value` at the start of a line (values of multi-line entries are indented or start with
+/// `"`); the value is the rest of the line.
+static KEY_RE: OnceLock<Regex> = OnceLock::new();
+
+/// Keys defined directly in `[section]`, with the first line of their value and their 1-based
+/// line numbers.
+fn section_entries(source: &str, section: &str) -> Vec<(String, String, u32)> {
+    let section_re = SECTION_RE.get_or_init(|| Regex::new(r"^\[([^\]]+)\]\s*$").unwrap());
+    let key_re =
+        KEY_RE.get_or_init(|| Regex::new(r"^([a-zA-Z_][a-zA-Z0-9_./]*)\s*=(.*)$").unwrap());
+    let mut entries = Vec::new();
+    let mut in_section = false;
+    for (i, line) in source.lines().enumerate() {
+        if let Some(cap) = section_re.captures(line) {
+            in_section = &cap[1] == section;
+        } else if in_section {
+            if let Some(cap) = key_re.captures(line) {
+                let value = cap[2].trim().to_string();
+                entries.push((cap[1].to_string(), value, (i + 1) as u32));
+            }
+        }
+    }
+    entries
+}
+
+/// Input action names declared in the `[input]` section, with their line numbers.
+pub fn find_input_actions(source: &str) -> Vec<(String, u32)> {
+    section_entries(source, "input")
+        .into_iter()
+        .map(|(name, _, line)| (name, line))
+        .collect()
+}
+
+/// Autoload singletons declared in the `[autoload]` section: (global name, `res://` path). The
+/// `*` marking an enabled singleton is dropped; entries that are not quoted strings are skipped.
+pub fn find_autoloads(source: &str) -> Vec<(String, String)> {
+    section_entries(source, "autoload")
+        .into_iter()
+        .filter_map(|(name, value, _)| {
+            let path = value.strip_prefix('"')?.strip_suffix('"')?;
+            Some((name, path.trim_start_matches('*').to_string()))
+        })
+        .collect()
+}
+
+/// `run/main_scene` from the `[application]` section, as written (`res://main.tscn`).
+pub fn find_main_scene(source: &str) -> Option<String> {
+    section_entries(source, "application")
+        .into_iter()
+        .find(|(key, _, _)| key == "run/main_scene")
+        .and_then(|(_, value, _)| Some(value.strip_prefix('"')?.strip_suffix('"')?.to_string()))
+}
+
+/// What gdcf reads from a project.godot, with `res://` paths resolved against the project
+/// directory (paths in any other form, e.g. `uid://`, are skipped).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectConfig {
+    /// Autoload singletons in declaration order: (global name, script or scene file).
+    pub autoloads: Vec<(String, PathBuf)>,
+    /// The scene the game starts with (`run/main_scene`).
+    pub main_scene: Option<PathBuf>,
+    /// Input action names with their line numbers.
+    pub input_actions: Vec<(String, u32)>,
+}
+
+impl ProjectConfig {
+    /// Parse project.godot `source`; `project_dir` is the directory `res://` refers to.
+    pub fn parse(source: &str, project_dir: &Path) -> Self {
+        let resolve = |path: &str| path.strip_prefix("res://").map(|rel| project_dir.join(rel));
+        ProjectConfig {
+            autoloads: find_autoloads(source)
+                .into_iter()
+                .filter_map(|(name, path)| Some((name, resolve(&path)?)))
+                .collect(),
+            main_scene: find_main_scene(source).and_then(|path| resolve(&path)),
+            input_actions: find_input_actions(source),
+        }
+    }
+
+    /// Read and parse the project file at `path`; `None` if it cannot be read.
+    pub fn from_file(path: &Path) -> Option<Self> {
+        let text = std::fs::read_to_string(path).ok()?;
+        let text = super::util::normalize_source(&text);
+        Some(Self::parse(&text, path.parent().unwrap_or(Path::new("."))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_input_actions_godot4() {
+        let src = r#"config_version=5
+
+[application]
+
+config/name="Demo"
+
+[input]
+
+jump={
+"deadzone": 0.5,
+"events": [Object(InputEventKey,"resource_local_to_scene":false,"keycode":32)
+]
+}
+move_left={
+"deadzone": 0.5,
+"events": []
+}
+
+[rendering]
+
+renderer/rendering_method="gl_compatibility"
+"#;
+        assert_eq!(
+            find_input_actions(src),
+            vec![("jump".to_string(), 9), ("move_left".to_string(), 14)]
+        );
+    }
+
+    #[test]
+    fn project_config_parses_representative_project() {
+        let src = r#"; Engine configuration file.
+config_version=5
+
+[application]
+
+config/name="Demo"
+run/main_scene="res://scenes/main.tscn"
+config/features=PackedStringArray("4.2", "Forward Plus")
+
+[autoload]
+
+AudioManager="*res://autoload/audio_manager.gd"
+Hud="*res://ui/hud.tscn"
+Legacy="uid://b8x3k2"
+
+[input]
+
+jump={
+"deadzone": 0.5,
+"events": []
+}
+"#;
+        let config = ProjectConfig::parse(src, Path::new("/game"));
+        assert_eq!(
+            config.autoloads,
+            vec![
+                (
+                    "AudioManager".to_string(),
+                    PathBuf::from("/game/autoload/audio_manager.gd")
+                ),
+                ("Hud".to_string(), PathBuf::from("/game/ui/hud.tscn")),
+            ]
+        );
+        assert_eq!(
+            config.main_scene,
+            Some(PathBuf::from("/game/scenes/main.tscn"))
+        );
+        assert_eq!(config.input_actions, vec![("jump".to_string(), 18)]);
+        assert_eq!(
+            ProjectConfig::parse("config_version=5\n", Path::new("/game")),
+            ProjectConfig::default()
+        );
+    }
+
+    #[test]
+    fn find_autoloads_strips_singleton_marker() {
+        let src = "[autoload]\n\nAudioManager=\"*res://autoload/audio_manager.gd\"\nEvents=\"res://events.gd\"\n\n[input]\n";
+        assert_eq!(
+            find_autoloads(src),
+            vec![
+                (
+                    "AudioManager".to_string(),
+                    "res://autoload/audio_manager.gd".to_string()
+                ),
+                ("Events".to_string(), "res://events.gd".to_string()),
+            ]
+        );
+    }
+}
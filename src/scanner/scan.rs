@@ -1,46 +1,628 @@
-//! Orchestrate directory scanning: .gd definitions/references and .tscn references.
+//! Orchestrate directory scanning: .gd definitions/references and .tscn/.tres references.
 
+use std::collections::{HashMap, HashSet};
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
 
-use super::files::{iter_gd_files, iter_tscn_files};
-use super::gd_definitions::find_function_definitions;
-use super::gd_references::find_function_references;
-use super::models::ScanResult;
-use super::tscn::find_tscn_references;
+use rayon::prelude::*;
+use regex::Regex;
+
+use super::cache::{config_fingerprint, fnv1a, FileScan, FileStamp, ScanCache};
+use super::files::{iter_files_by_extension, IgnorePatterns, WalkOptions};
+use super::gd_consts::{find_const_definitions, find_identifier_tokens};
+use super::gd_definitions::{find_class_name, find_function_definitions, has_ignore_file_marker};
+use super::gd_enums::find_enum_members;
+use super::gd_references::{
+    find_const_aliases, find_dynamic_calls, find_member_values, find_receiver_calls,
+    find_references, find_script_refs, find_static_method_values, retain_known_references,
+};
+use super::gd_signals::{find_signal_definitions, find_signal_references};
+use super::models::{AliasTarget, Confidence, RefKind, RefSite, Reference, ScanResult};
+use super::project_godot::{ProjectConfig, PROJECT_FILE};
+use super::tscn::{
+    find_tres_references, find_tscn_property_references, find_tscn_references,
+    find_tscn_signal_references,
+};
 use super::util::normalize_source;
 
+/// Options controlling a directory scan.
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptions {
+    /// Directory names (or **/name) to skip while walking.
+    pub exclude_dirs: Vec<String>,
+    /// Gitignore-style path globs (relative to the root) to skip, e.g. from `.gdcfignore`.
+    pub ignore_globs: Vec<String>,
+    /// Skip .tres resources (method names stored as strings are otherwise references).
+    pub no_tres: bool,
+    /// Directory holding the incremental cache; `None` disables caching. Files whose mtime and
+    /// size (or else content hash) match their entry are not re-parsed.
+    pub cache_dir: Option<PathBuf>,
+    /// .tscn node properties whose string value names a method (e.g. an exported `callback_name`).
+    pub tscn_ref_properties: Vec<String>,
+    /// Collect `const` declarations and identifier tokens (for unused-constant analysis).
+    pub consts: bool,
+    /// Collect enum members and identifier tokens (for unused-enum-member analysis).
+    pub enums: bool,
+    /// Also drop the references of files marked `# gdcf-ignore-file` (by default only their
+    /// definitions are skipped, so calls made from them still count).
+    pub no_ignored_file_refs: bool,
+    /// Regex for lines whose identifiers are not references (default [`DEFAULT_NOREF_PATTERN`]);
+    /// an invalid pattern falls back to the default.
+    pub noref_pattern: Option<String>,
+    /// Read autoload singletons from project.godot and resolve `Singleton.method()` calls to the
+    /// singleton's script only (otherwise they count for every `method` in the project).
+    pub autoloads: bool,
+    /// Project settings file read for autoloads and entry points; `None` reads
+    /// `<root>/project.godot` when present.
+    pub project_file: Option<PathBuf>,
+    /// Walk into symlinked directories (each real file is still scanned once).
+    pub follow_symlinks: bool,
+    /// Walk into hidden directories (`.git`, `.tools`); skipped by default.
+    pub include_hidden: bool,
+    /// Extra extensions (without the dot) of files scanned as GDScript, besides `gd`.
+    /// `tscn` and `tres` are ignored here, those files are always scanned as scenes/resources.
+    pub extensions: Vec<String>,
+}
+
+/// Suffixes of files scanned as GDScript for `extensions` (see [`ScanOptions::extensions`]):
+/// `.gd` first, then the extra ones lowercase and deduplicated.
+pub fn gdscript_suffixes(extensions: &[String]) -> Vec<String> {
+    let mut suffixes = vec![".gd".to_string()];
+    for ext in extensions {
+        let ext = ext.trim().trim_start_matches('.').to_lowercase();
+        let suffix = format!(".{}", ext);
+        if !ext.is_empty() && ext != "tscn" && ext != "tres" && !suffixes.contains(&suffix) {
+            suffixes.push(suffix);
+        }
+    }
+    suffixes
+}
+
+/// Per-line reference suppression: `foo() # gdcf-noref` does not keep `foo` alive.
+pub const DEFAULT_NOREF_PATTERN: &str = r"#\s*gdcf-noref\b";
+
+/// Compile `options.noref_pattern`, or the default when unset or invalid.
+fn noref_regex(options: &ScanOptions) -> Regex {
+    options
+        .noref_pattern
+        .as_deref()
+        .and_then(|p| Regex::new(p).ok())
+        .unwrap_or_else(|| Regex::new(DEFAULT_NOREF_PATTERN).unwrap())
+}
+
+/// Which parser a discovered file goes through.
+#[derive(Debug, Clone, Copy)]
+enum FileKind {
+    Gd,
+    Tscn,
+    Tres,
+}
+
 /// Read file and normalize for parsing (replace replacement char, normalize line endings/BOM).
-fn read_file_normalized(path: &Path) -> Option<String> {
-    let text = std::fs::read_to_string(path).ok()?;
+/// Files that are not valid UTF-8 (e.g. Windows-1252 exports) are decoded lossily rather than
+/// skipped; the flag is true for those.
+fn read_file_normalized(path: &Path) -> Option<(String, bool)> {
+    let bytes = std::fs::read(path).ok()?;
+    let (text, lossy) = match String::from_utf8(bytes) {
+        Ok(text) => (text, false),
+        Err(e) => (String::from_utf8_lossy(e.as_bytes()).into_owned(), true),
+    };
     let text = text.replace('\u{fffd}', "?"); // replace invalid UTF-8 like Python errors="replace"
-    Some(normalize_source(&text))
+    Some((normalize_source(&text), lossy))
 }
 
-/// Scan a directory for .gd and .tscn files; collect definitions from .gd and references from both.
+/// Lines of source files read on demand: each file is read (like the scan, see
+/// [`read_file_normalized`]) at most once.
+#[derive(Debug, Default)]
+pub(crate) struct SourceLines {
+    files: HashMap<PathBuf, Vec<String>>,
+}
+
+impl SourceLines {
+    /// The trimmed line `line` (1-based) of `path`; `None` if unreadable or past the end.
+    pub(crate) fn line(&mut self, path: &Path, line: u32) -> Option<&str> {
+        let lines = self.files.entry(path.to_path_buf()).or_insert_with(|| {
+            read_file_normalized(path).map_or_else(Vec::new, |(text, _)| {
+                text.lines().map(str::to_string).collect()
+            })
+        });
+        lines.get((line as usize).checked_sub(1)?).map(|l| l.trim())
+    }
+}
+
+/// Resolve a script path as written in GDScript (`res://...` or relative to `file`) to a file path.
+fn resolve_script_path(root: &Path, file: &Path, script: &Path) -> PathBuf {
+    let s = script.to_string_lossy();
+    let path = match s.strip_prefix("res://") {
+        Some(rel) => root.join(rel),
+        None => file.parent().unwrap_or(root).join(script),
+    };
+    path.canonicalize().unwrap_or(path)
+}
+
+/// Resolve a preload-style alias/extends target to a file path; class names are kept as-is.
+fn resolve_target(root: &Path, file: &Path, target: AliasTarget) -> AliasTarget {
+    match target {
+        AliasTarget::Script(script) => {
+            AliasTarget::Script(resolve_script_path(root, file, &script))
+        }
+        target => target,
+    }
+}
+
+/// Parse one .gd file: definitions, references, class_name, const aliases and script
+/// dependencies (script paths resolved); constants, enum members and identifier tokens when
+/// `options.consts` / `options.enums` ask for them. A file marked `# gdcf-ignore-file` contributes
+/// no definitions, and nothing at all with `options.no_ignored_file_refs`. Lines matching `noref`
+/// contribute no references.
+fn scan_gd_text(
+    root: &Path,
+    path: &Path,
+    text: &str,
+    options: &ScanOptions,
+    noref: &Regex,
+) -> FileScan {
+    let ignore_file = has_ignore_file_marker(text);
+    if ignore_file && options.no_ignored_file_refs {
+        return FileScan::default();
+    }
+    let const_aliases = find_const_aliases(path, text)
+        .into_iter()
+        .map(|(alias, target)| (alias, resolve_target(root, path, target)))
+        .collect();
+    let mut script_refs = find_script_refs(path, text);
+    script_refs.scripts = script_refs
+        .scripts
+        .iter()
+        .map(|script| resolve_script_path(root, path, script))
+        .collect();
+    script_refs.extends = script_refs
+        .extends
+        .map(|target| resolve_target(root, path, target));
+    let mut scan = FileScan {
+        definitions: find_function_definitions(path, text),
+        references: find_references(path, text),
+        class_name: find_class_name(text),
+        const_aliases,
+        script_refs: Some(script_refs),
+        signals: find_signal_definitions(path, text),
+        signal_references: find_signal_references(path, text),
+        consts: if options.consts {
+            find_const_definitions(path, text)
+        } else {
+            Vec::new()
+        },
+        enum_members: if options.enums {
+            find_enum_members(path, text)
+        } else {
+            Vec::new()
+        },
+        tokens: if options.consts || options.enums {
+            find_identifier_tokens(path, text)
+        } else {
+            Vec::new()
+        },
+        static_method_values: find_static_method_values(path, text),
+        member_values: find_member_values(path, text),
+        dynamic_calls: find_dynamic_calls(path, text),
+        receiver_calls: if options.autoloads {
+            find_receiver_calls(path, text)
+        } else {
+            Vec::new()
+        },
+    };
+    let noref_lines: HashSet<u32> = (1..)
+        .zip(text.split('\n'))
+        .filter(|(_, line)| noref.is_match(line))
+        .map(|(n, _)| n)
+        .collect();
+    if !noref_lines.is_empty() {
+        scan.references.retain(|r| !noref_lines.contains(&r.line));
+        scan.signal_references
+            .retain(|(_, line)| !noref_lines.contains(line));
+        scan.tokens.retain(|(_, line)| !noref_lines.contains(line));
+        scan.static_method_values
+            .retain(|(_, r)| !noref_lines.contains(&r.line));
+        scan.member_values
+            .retain(|r| !noref_lines.contains(&r.line));
+        scan.dynamic_calls
+            .retain(|(line, _)| !noref_lines.contains(line));
+        scan.receiver_calls
+            .retain(|(_, r)| !noref_lines.contains(&r.line));
+    }
+    if ignore_file {
+        scan.definitions.clear();
+        scan.signals.clear();
+        scan.consts.clear();
+        scan.enum_members.clear();
+    }
+    scan
+}
+
+/// Parse one .tscn file: signal connection references (handler methods and signals) and method
+/// names stored in `properties`.
+fn scan_tscn_text(path: &Path, text: &str, properties: &[String]) -> FileScan {
+    let references = find_tscn_references(path, text)
+        .into_iter()
+        .chain(find_tscn_property_references(path, text, properties))
+        .map(|(name, line)| Reference {
+            name,
+            line,
+            column: 0,
+            kind: RefKind::Qualified,
+            confidence: Confidence::Exact,
+        })
+        .collect();
+    FileScan {
+        references,
+        signal_references: find_tscn_signal_references(path, text),
+        ..FileScan::default()
+    }
+}
+
+/// Parse one .tres file: method names stored as strings only.
+fn scan_tres_text(path: &Path, text: &str) -> FileScan {
+    let references = find_tres_references(path, text)
+        .into_iter()
+        .map(|(name, line)| Reference {
+            name,
+            line,
+            column: 0,
+            kind: RefKind::Qualified,
+            confidence: Confidence::Exact,
+        })
+        .collect();
+    FileScan {
+        references,
+        ..FileScan::default()
+    }
+}
+
+/// Merge one file's parse results into the scan result. `Class.method` values and calls on
+/// capitalized receivers are queued in `deferred` until every `class_name` and autoload is known.
+fn merge_file_scan(result: &mut ScanResult, deferred: &mut Deferred, path: &Path, scan: FileScan) {
+    result.definitions.extend(scan.definitions);
+    for r in scan.references {
+        let site = RefSite {
+            path: path.to_path_buf(),
+            line: r.line,
+            column: r.column,
+            kind: r.kind,
+            confidence: r.confidence,
+            target: None,
+        };
+        result.add_ref_site(r.name, site);
+    }
+    result.signals.extend(scan.signals);
+    for (name, line) in scan.signal_references {
+        let site = RefSite {
+            path: path.to_path_buf(),
+            line,
+            column: 0,
+            kind: RefKind::Qualified,
+            confidence: Confidence::Exact,
+            target: None,
+        };
+        result
+            .signal_references
+            .entry(name)
+            .or_default()
+            .insert(site);
+    }
+    result.consts.extend(scan.consts);
+    result.enum_members.extend(scan.enum_members);
+    for (name, line) in scan.tokens {
+        let site = RefSite {
+            path: path.to_path_buf(),
+            line,
+            column: 0,
+            kind: RefKind::Bare,
+            confidence: Confidence::Heuristic,
+            target: None,
+        };
+        result
+            .token_references
+            .entry(name)
+            .or_default()
+            .insert(site);
+    }
+    for (line, text) in scan.dynamic_calls {
+        result.dynamic_calls.push((path.to_path_buf(), line, text));
+    }
+    for (class, r) in scan.static_method_values {
+        deferred.static_values.push((path.to_path_buf(), class, r));
+    }
+    for (receiver, r) in scan.receiver_calls {
+        deferred
+            .receiver_calls
+            .push((path.to_path_buf(), receiver, r));
+    }
+    if let Some(script_refs) = scan.script_refs {
+        result.script_refs.insert(path.to_path_buf(), script_refs);
+    }
+    if let Some(class_name) = scan.class_name {
+        result.class_names.insert(class_name, path.to_path_buf());
+    }
+    if !scan.const_aliases.is_empty() {
+        let aliases: HashMap<_, _> = scan.const_aliases.into_iter().collect();
+        result.const_aliases.insert(path.to_path_buf(), aliases);
+    }
+}
+
+/// Credit `Class.method` values whose class resolves (via `class_name` or a `const` alias in the
+/// using file) to a script defining `static func method`; other matches are member values.
+fn add_static_method_values(result: &mut ScanResult, values: Vec<(PathBuf, String, Reference)>) {
+    let static_defs: HashSet<(&Path, &str)> = result
+        .definitions
+        .iter()
+        .filter(|fd| fd.is_static)
+        .map(|fd| (fd.file.as_path(), fd.name.as_str()))
+        .collect();
+    let sites: Vec<_> = values
+        .into_iter()
+        .filter(|(path, class, r)| {
+            result
+                .resolve_class(path, class)
+                .is_some_and(|script| static_defs.contains(&(script, r.name.as_str())))
+        })
+        .collect();
+    for (path, _, r) in sites {
+        let site = RefSite {
+            path,
+            line: r.line,
+            column: r.column,
+            kind: r.kind,
+            confidence: r.confidence,
+            target: None,
+        };
+        result.add_ref_site(r.name, site);
+    }
+}
+
+/// With `options.autoloads`, record the project's autoload scripts (scene autoloads are skipped).
+/// Paths are canonical, like scanned files.
+fn apply_project_config(result: &mut ScanResult, project: &ProjectConfig, options: &ScanOptions) {
+    let canonical = |path: &PathBuf| path.canonicalize().unwrap_or_else(|_| path.clone());
+    if options.autoloads {
+        result.autoloads = project
+            .autoloads
+            .iter()
+            .filter(|(_, path)| path.extension().is_some_and(|ext| ext == "gd"))
+            .map(|(name, path)| (name.clone(), canonical(path)))
+            .collect();
+    }
+}
+
+/// Retarget `Singleton.method()` calls on an autoload (not shadowed by a `class_name` or a `const`
+/// alias in the calling file) to the singleton's script: the plain qualified site recorded for
+/// the call is replaced by one with [`RefSite::target`] set.
+fn target_autoload_calls(result: &mut ScanResult, calls: Vec<(PathBuf, String, Reference)>) {
+    for (path, receiver, r) in calls {
+        let Some(script) = result.autoloads.get(&receiver).cloned() else {
+            continue;
+        };
+        if result.resolve_class(&path, &receiver).is_some() {
+            continue;
+        }
+        let site = RefSite {
+            path,
+            line: r.line,
+            column: r.column,
+            kind: r.kind,
+            confidence: r.confidence,
+            target: None,
+        };
+        if let Some(sites) = result.references.get_mut(&r.name) {
+            if sites.remove(&site) {
+                sites.insert(RefSite {
+                    target: Some(script),
+                    ..site
+                });
+            }
+        }
+    }
+}
+
+/// File results that can only be resolved once every file is merged.
+#[derive(Default)]
+struct Deferred {
+    /// `Class.method` values: (file, class identifier, method reference).
+    static_values: Vec<(PathBuf, String, Reference)>,
+    /// Calls on capitalized receivers: (file, receiver, method reference).
+    receiver_calls: Vec<(PathBuf, String, Reference)>,
+}
+
+/// Scan a directory for .gd, .tscn and .tres files; collect definitions from .gd and references
+/// from all of them.
 pub fn scan_directory(
     root: &Path,
     debug_out: &mut Option<&mut dyn Write>,
     exclude_dirs: Option<&[String]>,
 ) -> ScanResult {
+    let options = ScanOptions {
+        exclude_dirs: exclude_dirs.unwrap_or(&[]).to_vec(),
+        ..ScanOptions::default()
+    };
+    scan_directory_with_progress(root, debug_out, &options, &mut |_, _| {})
+}
+
+/// Like [`scan_directory`], with caching and other options. With `cache_dir` set, files whose
+/// content hash matches the cache are not re-parsed; a config change invalidates the whole cache.
+/// Files are parsed in parallel; definitions are returned sorted by `(file, line)`.
+pub fn scan_directory_with_options(
+    root: &Path,
+    debug_out: &mut Option<&mut dyn Write>,
+    options: &ScanOptions,
+) -> ScanResult {
+    scan_directory_with_progress(root, debug_out, options, &mut |_, _| {})
+}
+
+/// Like [`scan_directory_with_options`], calling `progress(done, total)` after each file is read
+/// (or taken from the cache). Calls happen on the calling thread, in completion order.
+pub fn scan_directory_with_progress(
+    root: &Path,
+    debug_out: &mut Option<&mut dyn Write>,
+    options: &ScanOptions,
+    progress: &mut dyn FnMut(usize, usize),
+) -> ScanResult {
+    let exclude_dirs = Some(options.exclude_dirs.as_slice());
+    let mut cache = options
+        .cache_dir
+        .as_deref()
+        .map(|dir| ScanCache::load(dir, config_fingerprint(root, options)));
+    let mut seen = HashSet::new();
     let mut result = ScanResult::default();
-    for path in iter_gd_files(root, debug_out, exclude_dirs) {
-        let Some(text) = read_file_normalized(&path) else {
-            continue;
+    let noref = noref_regex(options);
+    // Walking (which writes the `[walk]` debug log) stays serial; only per-file work is parallel.
+    let ignore = IgnorePatterns::new(&options.ignore_globs);
+    let walk = WalkOptions {
+        follow_symlinks: options.follow_symlinks,
+        include_hidden: options.include_hidden,
+    };
+    let mut gd_files = Vec::new();
+    for suffix in gdscript_suffixes(&options.extensions) {
+        for path in iter_files_by_extension(root, debug_out, exclude_dirs, &ignore, &suffix, walk) {
+            // `--ext d` also matches `.gd` files; scan each file once.
+            if !gd_files.contains(&path) {
+                gd_files.push(path);
+            }
+        }
+    }
+    let tscn_files = iter_files_by_extension(root, debug_out, exclude_dirs, &ignore, ".tscn", walk);
+    let tres_files = if options.no_tres {
+        Vec::new()
+    } else {
+        iter_files_by_extension(root, debug_out, exclude_dirs, &ignore, ".tres", walk)
+    };
+    let files: Vec<(PathBuf, FileKind)> = gd_files
+        .into_iter()
+        .map(|p| (p, FileKind::Gd))
+        .chain(tscn_files.into_iter().map(|p| (p, FileKind::Tscn)))
+        .chain(tres_files.into_iter().map(|p| (p, FileKind::Tres)))
+        .collect();
+    // Read, hash and parse in parallel against a read-only cache; results keep file order.
+    // Workers report each finished file over a channel so `progress` runs on this thread.
+    let total = files.len();
+    let (done_tx, done_rx) = mpsc::channel::<()>();
+    let scan_file = |(path, kind): (PathBuf, FileKind)| {
+        // Canonicalize once here so analysis can compare `FunctionDef.file`/`RefSite.path` as-is.
+        let path = path.canonicalize().unwrap_or(path);
+        let stamp = FileStamp::of(&path);
+        let unchanged = stamp
+            .and_then(|stamp| cache.as_ref()?.get_unchanged(&path, stamp))
+            .cloned();
+        if let Some(file_scan) = unchanged {
+            return Ok((path, None, file_scan, false, false));
+        }
+        let Some((text, lossy)) = read_file_normalized(&path) else {
+            return Err(path);
+        };
+        let content_hash = fnv1a(text.as_bytes());
+        let cached = cache
+            .as_ref()
+            .and_then(|c| c.get(&path, content_hash))
+            .cloned();
+        let (file_scan, parsed) = match (cached, kind) {
+            (Some(file_scan), _) => (file_scan, false),
+            (None, FileKind::Gd) => (scan_gd_text(root, &path, &text, options, &noref), true),
+            (None, FileKind::Tscn) => (
+                scan_tscn_text(&path, &text, &options.tscn_ref_properties),
+                true,
+            ),
+            (None, FileKind::Tres) => (scan_tres_text(&path, &text), true),
         };
-        for fd in find_function_definitions(&path, &text) {
-            result.definitions.push(fd);
+        Ok((path, Some((content_hash, stamp)), file_scan, parsed, lossy))
+    };
+    let results: Vec<_> = std::thread::scope(|s| {
+        let workers = s.spawn(|| {
+            files
+                .into_par_iter()
+                .map_with(done_tx, |done_tx, file| {
+                    let scanned = scan_file(file);
+                    let _ = done_tx.send(());
+                    scanned
+                })
+                .collect()
+        });
+        // Ends once every worker's sender is dropped, i.e. when all files are done.
+        for (done, ()) in done_rx.iter().enumerate() {
+            progress(done + 1, total);
         }
-        for (name, line) in find_function_references(&path, &text) {
-            result.add_reference(name, path.clone(), line);
+        workers.join().expect("scan worker panicked")
+    });
+    let mut scanned = Vec::with_capacity(results.len());
+    for scan in results {
+        match scan {
+            Ok(scan) => scanned.push(scan),
+            Err(path) => {
+                if let Some(out) = debug_out.as_mut() {
+                    let _ = writeln!(out, "  [read] failed to read {:?}; skipped", path);
+                }
+                result.skipped_files.push(path);
+            }
         }
     }
-    for path in iter_tscn_files(root, debug_out, exclude_dirs) {
-        let Some(text) = read_file_normalized(&path) else {
-            continue;
-        };
-        for (name, line) in find_tscn_references(&path, &text) {
-            result.add_reference(name, path.clone(), line);
+    result.skipped_files.sort();
+    let project_file = options
+        .project_file
+        .clone()
+        .unwrap_or_else(|| root.join(PROJECT_FILE));
+    if let Some(project) = ProjectConfig::from_file(&project_file) {
+        apply_project_config(&mut result, &project, options);
+    }
+    // Second pass: with every definition known, loose (heuristic) matches only count when they
+    // name a function. The cache keeps them unfiltered, since other files define the names.
+    let known: HashSet<String> = scanned
+        .iter()
+        .flat_map(|(_, _, file_scan, _, _)| file_scan.definitions.iter())
+        .map(|fd| fd.name.clone())
+        .collect();
+    let mut deferred = Deferred::default();
+    for (path, read, mut file_scan, parsed, lossy) in scanned {
+        if parsed {
+            result.parsed_files += 1;
+        }
+        if lossy {
+            if let Some(out) = debug_out.as_mut() {
+                let _ = writeln!(
+                    out,
+                    "  [read] {:?} is not valid UTF-8; invalid bytes read as '?'",
+                    path
+                );
+            }
+        }
+        // Read files get a fresh entry: re-parsed, or touched but unchanged (new stamp).
+        if let (Some(cache), Some((content_hash, stamp))) = (cache.as_mut(), read) {
+            cache.insert(path.clone(), content_hash, stamp, file_scan.clone());
+        }
+        seen.insert(path.clone());
+        file_scan.references.append(&mut file_scan.member_values);
+        retain_known_references(&mut file_scan.references, &known);
+        merge_file_scan(&mut result, &mut deferred, &path, file_scan);
+    }
+    add_static_method_values(&mut result, deferred.static_values);
+    target_autoload_calls(&mut result, deferred.receiver_calls);
+    result
+        .definitions
+        .sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+    result
+        .signals
+        .sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+    result
+        .consts
+        .sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+    result.dynamic_calls.sort();
+    // Stable sort: members on one line keep their declaration order.
+    result
+        .enum_members
+        .sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+    if let (Some(cache), Some(dir)) = (cache.as_mut(), options.cache_dir.as_deref()) {
+        cache.retain_paths(&seen);
+        if let Err(e) = cache.save(dir) {
+            if let Some(out) = debug_out.as_mut() {
+                let _ = writeln!(out, "  [cache] failed to write {:?}: {}", dir, e);
+            }
         }
     }
     result
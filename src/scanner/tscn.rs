@@ -1,4 +1,4 @@
-//! Find signal connection references in .tscn files.
+//! Find method-name references in scene (.tscn) and resource (.tres) files.
 
 use std::path::Path;
 
@@ -12,6 +12,27 @@ fn tscn_method_re() -> &'static Regex {
         .get_or_init(|| Regex::new(r#"method\s*=\s*["']([a-zA-Z_][a-zA-Z0-9_]*)["']"#).unwrap())
 }
 
+/// .tres/.tscn property whose whole value is a quoted identifier: `state_enter = "_on_enter"`
+/// (also `&"..."`); captures the property name and the value
+static STRING_VALUE_RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+
+fn string_value_re() -> &'static Regex {
+    STRING_VALUE_RE.get_or_init(|| {
+        Regex::new(
+            r#"(?m)^[ \t]*([a-zA-Z_][a-zA-Z0-9_/]*)[ \t]*=[ \t]*&?"([a-zA-Z_][a-zA-Z0-9_]*)"[ \t]*$"#,
+        )
+        .unwrap()
+    })
+}
+
+/// Godot .tscn [connection signal="name" ...] – the connected signal
+static TSCN_SIGNAL_RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+
+fn tscn_signal_re() -> &'static Regex {
+    TSCN_SIGNAL_RE
+        .get_or_init(|| Regex::new(r#"signal\s*=\s*["']([a-zA-Z_][a-zA-Z0-9_]*)["']"#).unwrap())
+}
+
 /// Find signal connection method names in a .tscn file.
 /// Returns list of (function_name, line_number) for each method="..." in [connection] blocks.
 pub fn find_tscn_references(_path: &Path, source: &str) -> Vec<(String, u32)> {
@@ -23,3 +44,53 @@ pub fn find_tscn_references(_path: &Path, source: &str) -> Vec<(String, u32)> {
     }
     refs
 }
+
+/// Find method names stored in the given node properties of a .tscn file, e.g. an exported
+/// `callback_name = "fire"` set in the inspector. Returns list of (function_name, line_number).
+pub fn find_tscn_property_references(
+    _path: &Path,
+    source: &str,
+    properties: &[String],
+) -> Vec<(String, u32)> {
+    if properties.is_empty() {
+        return Vec::new();
+    }
+    let line_at = |pos: usize| -> u32 { (source[..pos].matches('\n').count() + 1) as u32 };
+    string_value_re()
+        .captures_iter(source)
+        .filter(|cap| properties.iter().any(|p| p == &cap[1]))
+        .map(|cap| {
+            let m = cap.get(2).unwrap();
+            (m.as_str().to_string(), line_at(m.start()))
+        })
+        .collect()
+}
+
+/// Find connected signal names in a .tscn file.
+/// Returns list of (signal_name, line_number) for each signal="..." in [connection] blocks.
+pub fn find_tscn_signal_references(_path: &Path, source: &str) -> Vec<(String, u32)> {
+    let line_at = |pos: usize| -> u32 { (source[..pos].matches('\n').count() + 1) as u32 };
+    tscn_signal_re()
+        .captures_iter(source)
+        .map(|cap| {
+            let m = cap.get(1).unwrap();
+            (m.as_str().to_string(), line_at(m.start()))
+        })
+        .collect()
+}
+
+/// Find method names stored as strings in a .tres resource (e.g. state machine resources):
+/// `method = "..."` plus any property whose value is a single quoted identifier.
+/// Returns list of (function_name, line_number).
+pub fn find_tres_references(path: &Path, source: &str) -> Vec<(String, u32)> {
+    let line_at = |pos: usize| -> u32 { (source[..pos].matches('\n').count() + 1) as u32 };
+    let mut refs = find_tscn_references(path, source);
+    for cap in string_value_re().captures_iter(source) {
+        let m = cap.get(2).unwrap();
+        let site = (m.as_str().to_string(), line_at(m.start()));
+        if !refs.contains(&site) {
+            refs.push(site);
+        }
+    }
+    refs
+}
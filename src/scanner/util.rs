@@ -1,4 +1,4 @@
-//! Shared utilities for source normalization.
+//! Shared utilities for source normalization and name matching.
 
 /// Normalize line endings and BOM so regex and line counts are consistent across platforms.
 pub fn normalize_source(text: &str) -> String {
@@ -12,6 +12,31 @@ pub fn normalize_source(text: &str) -> String {
     s
 }
 
+/// 1-based (line, column) of byte offset `pos`; the column counts chars, not bytes, since the
+/// last newline.
+pub fn line_col(source: &str, pos: usize) -> (u32, u32) {
+    let before = &source[..pos];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line as u32, column as u32)
+}
+
+/// Levenshtein distance between two names (insertions, deletions, substitutions; by char).
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -32,4 +57,22 @@ mod tests {
         assert!(!normalize_source("a\r\nb\r\nc").contains('\r'));
         assert_eq!(normalize_source("a\r\nb"), "a\nb");
     }
+
+    #[test]
+    fn edit_distance_basic() {
+        assert_eq!(edit_distance("foo", "foo"), 0);
+        assert_eq!(edit_distance("foo", "fooo"), 1);
+        assert_eq!(edit_distance("foo", "fo"), 1);
+        assert_eq!(edit_distance("foo", "fob"), 1);
+        assert_eq!(edit_distance("foo", "bar"), 3);
+        assert_eq!(edit_distance("", "ab"), 2);
+    }
+
+    #[test]
+    fn line_col_counts_chars() {
+        let src = "a\nżółw foo";
+        assert_eq!(line_col(src, 0), (1, 1));
+        assert_eq!(line_col(src, 2), (2, 1));
+        assert_eq!(line_col(src, src.find("foo").unwrap()), (2, 6));
+    }
 }
@@ -1,4 +1,5 @@
-//! Integration tests for find_unused_functions, find_only_test_referenced_functions, default_is_test_path.
+//! Integration tests for find_unused_functions, find_only_test_referenced_functions,
+//! find_unused_signals, default_is_test_path.
 
 use std::path::Path;
 
@@ -6,9 +7,30 @@ mod common;
 use common::project;
 
 use gdcf::scanner::{
-    default_is_test_path, find_only_test_referenced_functions, find_unused_functions,
+    analyze, default_is_test_path, find_duplicate_definitions, find_only_test_referenced_functions,
+    find_unreachable_functions, find_unused_consts, find_unused_enum_members,
+    find_unused_functions, find_unused_functions_with, find_unused_signals, scan_directory,
+    scan_directory_with_options, AnalysisOptions, AnalyzeOptions, Confidence, ScanOptions,
 };
 
+fn unused_names_scoped(root: &Path) -> Vec<String> {
+    let scan = scan_directory(root, &mut None, None);
+    let options = AnalysisOptions {
+        scoped: true,
+        ..AnalysisOptions::default()
+    };
+    find_unused_functions_with(&scan, &options)
+        .into_iter()
+        .map(|f| {
+            format!(
+                "{}:{}",
+                f.file.file_name().unwrap().to_string_lossy(),
+                f.name
+            )
+        })
+        .collect()
+}
+
 #[test]
 fn find_unused_functions_bootstrap() {
     let (_dir, root) = project(&[(
@@ -64,6 +86,73 @@ func actually_unused():
     assert!(names.contains(&"actually_unused"));
 }
 
+#[test]
+fn find_unused_functions_bound_callable_is_used() {
+    let (_dir, root) = project(&[(
+        "main.gd",
+        r#"extends Node
+
+func _ready():
+    var cb = my_handler.bind(42)
+    cb.call()
+
+func my_handler(value):
+    print(value)
+"#,
+    )]);
+    let unused = find_unused_functions(&root, None, None);
+    assert!(!unused.iter().any(|f| f.name == "my_handler"));
+}
+
+#[test]
+fn scoped_bare_call_only_credits_same_file() {
+    let (_dir, root) = project(&[
+        (
+            "a.gd",
+            "extends Node\nfunc _ready():\n    _helper()\nfunc _helper():\n    pass\n",
+        ),
+        ("b.gd", "extends Node\nfunc _helper():\n    pass\n"),
+    ]);
+    let default_unused = find_unused_functions(&root, None, None);
+    assert!(
+        default_unused.is_empty(),
+        "name-based matching credits both _helper definitions"
+    );
+    assert_eq!(unused_names_scoped(&root), vec!["b.gd:_helper"]);
+}
+
+#[test]
+fn scoped_bare_call_credits_inherited_and_referenced_scripts() {
+    let (_dir, root) = project(&[
+        (
+            "base.gd",
+            "class_name Base\nextends Node\nfunc base_helper():\n    pass\n",
+        ),
+        (
+            "child.gd",
+            "extends Base\nfunc _ready():\n    base_helper()\n",
+        ),
+        ("tools.gd", "extends Node\nfunc tool_helper():\n    pass\n"),
+        (
+            "user.gd",
+            "extends Node\nconst Tools = preload(\"res://tools.gd\")\nfunc _ready():\n    tool_helper()\n",
+        ),
+    ]);
+    assert!(unused_names_scoped(&root).is_empty());
+}
+
+#[test]
+fn scoped_qualified_call_still_counts_everywhere() {
+    let (_dir, root) = project(&[
+        ("player.gd", "extends Node\nfunc jump():\n    pass\n"),
+        (
+            "main.gd",
+            "extends Node\nfunc _ready():\n    $Player.jump()\n",
+        ),
+    ]);
+    assert!(unused_names_scoped(&root).is_empty());
+}
+
 #[test]
 fn test_default_is_test_path() {
     let (_dir, root) = project(&[
@@ -255,3 +344,795 @@ fn default_is_test_path_case_insensitive() {
     assert!(default_is_test_path(&root, &root.join("Test_Something.gd")));
     assert!(default_is_test_path(&root, &root.join("something_Test.gd")));
 }
+
+#[test]
+fn find_unused_signals_emit_connect_and_tscn() {
+    let (_dir, root) = project(&[
+        (
+            "player.gd",
+            r#"extends Node
+signal died
+signal hit(damage: int)
+signal moved
+signal legacy
+signal from_scene
+signal never_used(a, b)
+signal kept # gdcf-ignore
+func take(d):
+    hit.emit(d)
+    emit_signal("died")
+    connect("legacy", self, "_on_legacy")
+"#,
+        ),
+        (
+            "hud.gd",
+            "extends Node
+func _ready():
+    $P.moved.connect(_x)
+",
+        ),
+        (
+            "main.tscn",
+            "[connection signal=\"from_scene\" from=\"P\" to=\".\" method=\"_on_x\"]\n",
+        ),
+    ]);
+    let scan = scan_directory(&root, &mut None, None);
+    let names: Vec<_> = find_unused_signals(&scan)
+        .into_iter()
+        .map(|s| (s.name, s.line))
+        .collect();
+    assert_eq!(names, vec![("never_used".to_string(), 7)]);
+}
+
+#[test]
+fn find_unused_functions_native_callback_is_used() {
+    let (_dir, root) = project(&[(
+        "ext.gd",
+        "extends Node\nfunc _on_native_tick(dt):\n    pass\nfunc dead():\n    pass\n",
+    )]);
+    let scan = scan_directory(&root, &mut None, None);
+    let options = AnalysisOptions {
+        native_callbacks: vec!["_on_native_tick".to_string()],
+        ..AnalysisOptions::default()
+    };
+    let names: Vec<_> = find_unused_functions_with(&scan, &options)
+        .into_iter()
+        .map(|f| f.name)
+        .collect();
+    assert_eq!(names, vec!["dead"]);
+}
+
+#[test]
+fn find_unused_functions_virtual_method_is_used() {
+    let (_dir, root) = project(&[
+        (
+            "spawner.gd",
+            "class_name Spawnable\nextends Node\nfunc _on_spawn():\n    pass\n",
+        ),
+        (
+            "enemy.gd",
+            "extends Spawnable\nfunc _on_spawn():\n    pass\nfunc _ready():\n    pass\n",
+        ),
+    ]);
+    let scan = scan_directory(&root, &mut None, None);
+    let unused = |virtual_methods: Vec<String>| -> Vec<String> {
+        let options = AnalysisOptions {
+            virtual_methods,
+            ..AnalysisOptions::default()
+        };
+        find_unused_functions_with(&scan, &options)
+            .into_iter()
+            .map(|f| f.name)
+            .collect()
+    };
+    assert_eq!(unused(Vec::new()), vec!["_on_spawn", "_on_spawn"]);
+    assert!(unused(vec!["_on_spawn".to_string()]).is_empty());
+}
+
+#[test]
+fn find_unused_functions_same_results_for_canonical_and_dotted_root() {
+    let (_dir, root) = project(&[
+        (
+            "main.gd",
+            "extends Node\nfunc _ready():\n    Util.helper()\n",
+        ),
+        (
+            "a/b/c/util.gd",
+            "class_name Util\nstatic func helper():\n    pass\nfunc dead():\n    pass\n",
+        ),
+        (
+            "a/b/other.gd",
+            "func dead():\n    pass\nfunc also_dead():\n    pass\n",
+        ),
+        (
+            "tests/test_util.gd",
+            "func test_it():\n    Util.new().dead()\n",
+        ),
+    ]);
+    let canonical = root.canonicalize().unwrap();
+    let dotted = canonical.join("a").join("..").join(".");
+    let summary = |root: &Path| {
+        let scan = scan_directory(root, &mut None, None);
+        for fd in &scan.definitions {
+            assert_eq!(fd.file, fd.file.canonicalize().unwrap());
+        }
+        let unused: Vec<_> = find_unused_functions(root, Some(&scan), None)
+            .into_iter()
+            .map(|f| (f.file, f.line, f.name))
+            .collect();
+        let only_tests: Vec<_> = find_only_test_referenced_functions(root, None, Some(&scan), None)
+            .into_iter()
+            .map(|f| (f.file, f.line, f.name))
+            .collect();
+        (unused, only_tests)
+    };
+    let (unused, only_tests) = summary(&canonical);
+    assert_eq!(summary(&dotted), (unused.clone(), only_tests.clone()));
+    let names: Vec<_> = unused.iter().map(|u| u.2.as_str()).collect();
+    assert_eq!(names, vec!["also_dead"]);
+    let names: Vec<_> = only_tests.iter().map(|u| u.2.as_str()).collect();
+    assert_eq!(names, vec!["dead", "dead"]);
+}
+
+#[test]
+fn find_unused_functions_await_signal_is_not_a_call() {
+    let (_dir, root) = project(&[
+        (
+            "waiter.gd",
+            r#"extends Node
+@onready var obj = $Child
+func _ready():
+    await obj.ready
+    await get_tree().process_frame
+    await ready
+"#,
+        ),
+        (
+            "child.gd",
+            "extends Node\nfunc ready():\n    pass\nfunc process_frame():\n    pass\n",
+        ),
+    ]);
+    let mut names: Vec<_> = find_unused_functions(&root, None, None)
+        .into_iter()
+        .map(|f| f.name)
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["process_frame", "ready"]);
+}
+
+#[test]
+fn find_unused_functions_tween_callback_bare_arg_is_used() {
+    let (_dir, root) = project(&[(
+        "fader.gd",
+        r#"extends Node
+func _ready():
+    var tween = create_tween()
+    tween.tween_property(self, "modulate:a", 0.0, 1.0)
+    tween.tween_callback(on_done)
+    create_tween().tween_callback(on_chained)
+func on_done():
+    pass
+func on_chained():
+    pass
+func not_a_callback():
+    pass
+"#,
+    )]);
+    let names: Vec<_> = find_unused_functions(&root, None, None)
+        .into_iter()
+        .map(|f| f.name)
+        .collect();
+    assert_eq!(names, vec!["not_a_callback"]);
+}
+
+#[test]
+fn analyze_returns_findings_and_reference_map() {
+    let (_dir, root) = project(&[
+        (
+            "main.gd",
+            "extends Node\nfunc _ready():\n    used()\n    used()\nfunc used():\n    pass\nfunc dead():\n    pass\nfunc helper():\n    pass\n",
+        ),
+        ("tests/test_main.gd", "func test_it():\n    helper()\n"),
+    ]);
+    let analysis = analyze(&root, &AnalyzeOptions::default());
+    let names =
+        |defs: &[gdcf::FunctionDef]| defs.iter().map(|f| f.name.clone()).collect::<Vec<_>>();
+    assert_eq!(names(&analysis.unused), vec!["dead"]);
+    assert_eq!(names(&analysis.test_only), vec!["helper"]);
+    let used: Vec<_> = analysis.reference_map["used"]
+        .iter()
+        .map(|r| r.line)
+        .collect();
+    assert_eq!(used, vec![3, 4]);
+    assert!(analysis.reference_map["dead"].is_empty());
+    let helper = &analysis.reference_map["helper"];
+    assert_eq!(helper.len(), 1);
+    assert!(helper[0].path.ends_with("tests/test_main.gd"));
+}
+
+#[test]
+fn find_unused_functions_get_node_or_null_method_call() {
+    let (_dir, root) = project(&[
+        (
+            "hud.gd",
+            "extends Node\nfunc _process(_d):\n    get_node_or_null(\"X\").refresh()\n",
+        ),
+        ("x.gd", "extends Node\nfunc refresh():\n    pass\n"),
+    ]);
+    assert!(find_unused_functions(&root, None, None).is_empty());
+}
+
+#[test]
+fn find_unused_functions_property_accessors_are_used() {
+    let (_dir, root) = project(&[(
+        "stats.gd",
+        r#"extends Node
+var health: int = 10: get = _get_health, set = _set_health
+var mana: int:
+    get = _get_mana
+    set = _set_mana
+var _h := 0
+func _get_health():
+    return _h
+func _set_health(v):
+    _h = v
+func _get_mana():
+    return 0
+func _set_mana(_v):
+    pass
+func unrelated():
+    pass
+"#,
+    )]);
+    let names: Vec<_> = find_unused_functions(&root, None, None)
+        .into_iter()
+        .map(|f| f.name)
+        .collect();
+    assert_eq!(names, vec!["unrelated"]);
+}
+
+#[test]
+fn find_unused_functions_setget_accessors_are_used() {
+    let (_dir, root) = project(&[(
+        "mover.gd",
+        r#"extends Node
+var speed = 1.0 setget set_speed, get_speed
+var label = "" setget set_label
+var ratio = 0.5 setget , get_ratio
+func set_speed(v):
+    speed = v
+func get_speed():
+    return speed
+func set_label(v):
+    label = v
+func get_ratio():
+    return ratio
+func unrelated():
+    pass
+"#,
+    )]);
+    let names: Vec<_> = find_unused_functions(&root, None, None)
+        .into_iter()
+        .map(|f| f.name)
+        .collect();
+    assert_eq!(names, vec!["unrelated"]);
+}
+
+#[test]
+fn find_unused_functions_comparator_callbacks_are_used() {
+    let (_dir, root) = project(&[(
+        "sorter.gd",
+        r#"extends Node
+func _ready():
+    var arr = [3, 1, 2]
+    arr.sort_custom(_cmp)
+    var i = arr.bsearch_custom(2, _cmp_search)
+func _cmp(a, b):
+    return a < b
+func _cmp_search(a, b):
+    return a < b
+func unrelated():
+    pass
+"#,
+    )]);
+    let names: Vec<_> = find_unused_functions(&root, None, None)
+        .into_iter()
+        .map(|f| f.name)
+        .collect();
+    assert_eq!(names, vec!["unrelated"]);
+}
+
+#[test]
+fn find_unused_functions_skips_rpc_methods() {
+    let (_dir, root) = project(&[(
+        "player.gd",
+        r#"extends Node
+@rpc
+func sync_position(pos):
+    pass
+@rpc("any_peer")
+func take_damage(amount):
+    pass
+func unrelated():
+    pass
+"#,
+    )]);
+    let names: Vec<_> = find_unused_functions(&root, None, None)
+        .into_iter()
+        .map(|f| f.name)
+        .collect();
+    assert_eq!(names, vec!["unrelated"]);
+}
+
+#[test]
+fn find_only_test_referenced_functions_skips_rpc_methods() {
+    let (_dir, root) = project(&[
+        (
+            "player.gd",
+            "extends Node\n@rpc\nfunc sync_position(pos):\n    pass\nfunc helper():\n    pass\n",
+        ),
+        (
+            "tests/test_player.gd",
+            "func test_it():\n    sync_position(1)\n    helper()\n",
+        ),
+    ]);
+    let names: Vec<_> = find_only_test_referenced_functions(&root, None, None, None)
+        .into_iter()
+        .map(|f| f.name)
+        .collect();
+    assert_eq!(names, vec!["helper"]);
+}
+
+#[test]
+fn find_unused_functions_var_initializers_are_used() {
+    let (_dir, root) = project(&[(
+        "hud.gd",
+        r#"extends Node
+@onready var cb := _on_something
+@export var typed_cb: Callable = some_func
+func _on_something():
+    pass
+func some_func():
+    pass
+func unrelated():
+    pass
+"#,
+    )]);
+    let names: Vec<_> = find_unused_functions(&root, None, None)
+        .into_iter()
+        .map(|f| f.name)
+        .collect();
+    assert_eq!(names, vec!["unrelated"]);
+}
+
+const TWEEN_METHOD_FIXTURE: &str = r#"extends Node
+func set_master_volume(volume_db: float) -> void:
+    master_volume = volume_db
+
+func transition_master_volume(from_volume: float, to_volume: float) -> void:
+    _fade_tween = create_tween()
+    _fade_tween.tween_method(set_master_volume, from_volume, to_volume, 0.5)
+
+func _ready():
+    transition_master_volume(0.0, 1.0)
+"#;
+
+fn unused_names_at(root: &Path, min_confidence: Option<Confidence>) -> Vec<String> {
+    let scan = scan_directory(root, &mut None, None);
+    let options = AnalysisOptions {
+        min_confidence,
+        ..AnalysisOptions::default()
+    };
+    find_unused_functions_with(&scan, &options)
+        .into_iter()
+        .map(|f| f.name)
+        .collect()
+}
+
+#[test]
+fn find_unused_functions_min_confidence_heuristic_counts_first_arg() {
+    let (_dir, root) = project(&[("audio.gd", TWEEN_METHOD_FIXTURE)]);
+    assert!(unused_names_at(&root, None).is_empty());
+    assert!(unused_names_at(&root, Some(Confidence::Heuristic)).is_empty());
+}
+
+#[test]
+fn find_unused_functions_min_confidence_exact_ignores_first_arg() {
+    let (_dir, root) = project(&[("audio.gd", TWEEN_METHOD_FIXTURE)]);
+    assert_eq!(
+        unused_names_at(&root, Some(Confidence::Exact)),
+        vec!["set_master_volume"],
+        "tween_method(set_master_volume, ...) is only a heuristic reference"
+    );
+}
+
+#[test]
+fn find_unused_functions_method_call_after_cast_is_used() {
+    let (_dir, root) = project(&[
+        (
+            "player.gd",
+            "class_name Player\nextends Node\nfunc jump():\n    pass\n",
+        ),
+        (
+            "main.gd",
+            "extends Node\nfunc _ready():\n    (get_node(\"Player\") as Player).jump()\n",
+        ),
+    ]);
+    let names: Vec<_> = find_unused_functions(&root, None, None)
+        .into_iter()
+        .map(|f| f.name)
+        .collect();
+    assert!(names.is_empty(), "unused: {:?}", names);
+}
+
+#[test]
+fn find_duplicate_definitions_groups_same_name_across_files() {
+    let (_dir, root) = project(&[
+        (
+            "autoload_a.gd",
+            "extends Node\nfunc _ready():\n    pass\nfunc format_time(t):\n    return t\n",
+        ),
+        (
+            "autoload_b.gd",
+            "extends Node\nfunc _ready():\n    pass\n\nfunc format_time(t):\n    return t\n",
+        ),
+        (
+            "single.gd",
+            "extends Node\nfunc helper():\n    pass\nclass Inner:\n    func helper():\n        pass\n",
+        ),
+    ]);
+    let scan = scan_directory(&root, &mut None, None);
+    let groups: Vec<Vec<_>> = find_duplicate_definitions(&scan)
+        .into_iter()
+        .map(|group| {
+            group
+                .into_iter()
+                .map(|fd| {
+                    let file = fd.file.file_name().unwrap().to_string_lossy().to_string();
+                    (fd.name, file, fd.line)
+                })
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        groups,
+        vec![vec![
+            ("format_time".to_string(), "autoload_a.gd".to_string(), 4),
+            ("format_time".to_string(), "autoload_b.gd".to_string(), 5),
+        ]]
+    );
+}
+
+#[test]
+fn find_duplicate_definitions_skips_overrides() {
+    let (_dir, root) = project(&[
+        (
+            "base.gd",
+            "class_name Base\nextends Node\nfunc take_damage(n):\n    pass\nfunc describe():\n    pass\n",
+        ),
+        (
+            "enemy.gd",
+            "extends Base\nfunc take_damage(n):\n    pass\nfunc describe():\n    pass\n",
+        ),
+        (
+            "boss.gd",
+            "extends \"res://enemy.gd\"\nfunc take_damage(n):\n    pass\n",
+        ),
+        ("pickup.gd", "extends Node\nfunc describe():\n    pass\n"),
+    ]);
+    let scan = scan_directory(&root, &mut None, None);
+    let groups: Vec<Vec<_>> = find_duplicate_definitions(&scan)
+        .into_iter()
+        .map(|group| {
+            group
+                .into_iter()
+                .map(|fd| {
+                    (
+                        fd.name,
+                        fd.file.file_name().unwrap().to_string_lossy().to_string(),
+                    )
+                })
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        groups,
+        vec![vec![
+            ("describe".to_string(), "base.gd".to_string()),
+            ("describe".to_string(), "enemy.gd".to_string()),
+            ("describe".to_string(), "pickup.gd".to_string()),
+        ]],
+        "take_damage is only overridden along one hierarchy"
+    );
+}
+
+#[test]
+fn find_unused_consts_used_and_unused() {
+    let (_dir, root) = project(&[
+        (
+            "config.gd",
+            r#"class_name Config
+extends Node
+const MAX_SPEED := 10.0
+const GRAVITY: float = 9.8
+const UNUSED_LIMIT = 3
+const LABEL = "MAX_SPEED" # the string does not count
+"#,
+        ),
+        (
+            "player.gd",
+            "extends Node\nfunc _physics_process(delta):\n    velocity.y += Config.GRAVITY * delta\n    speed = min(speed, Config.MAX_SPEED)\n    print(Config.LABEL)\n",
+        ),
+    ]);
+    let options = ScanOptions {
+        consts: true,
+        ..ScanOptions::default()
+    };
+    let scan = scan_directory_with_options(&root, &mut None, &options);
+    let names: Vec<_> = find_unused_consts(&scan)
+        .into_iter()
+        .map(|c| c.name)
+        .collect();
+    assert_eq!(names, vec!["UNUSED_LIMIT"]);
+
+    let scan = scan_directory(&root, &mut None, None);
+    assert!(
+        scan.consts.is_empty(),
+        "consts are only collected on request"
+    );
+}
+
+#[test]
+fn find_unused_enum_members_qualified_unqualified_and_unused() {
+    let (_dir, root) = project(&[
+        (
+            "player.gd",
+            r#"class_name Player
+extends Node
+enum State { IDLE, RUN, JUMP }
+var state = State.IDLE
+func _physics_process(_delta):
+    if state == RUN:
+        pass
+"#,
+        ),
+        (
+            "hud.gd",
+            "extends Node\nfunc show(p):\n    return p.state == Player.State.RUN\n",
+        ),
+    ]);
+    let options = ScanOptions {
+        enums: true,
+        ..ScanOptions::default()
+    };
+    let scan = scan_directory_with_options(&root, &mut None, &options);
+    assert_eq!(scan.enum_members.len(), 3);
+    let names: Vec<_> = find_unused_enum_members(&scan)
+        .into_iter()
+        .map(|m| m.qualified_name())
+        .collect();
+    assert_eq!(names, vec!["State.JUMP"]);
+}
+
+#[test]
+fn find_unused_functions_has_method_dispatch_is_used() {
+    let (_dir, root) = project(&[
+        (
+            "plugin.gd",
+            r#"extends Node
+const HOOK = "do_it"
+func run_hook(target):
+    if target.has_method("do_it"):
+        target.call(HOOK)
+func _ready():
+    run_hook(self)
+"#,
+        ),
+        ("handler.gd", "extends Node\nfunc do_it():\n    pass\n"),
+    ]);
+    let names: Vec<_> = find_unused_functions(&root, None, None)
+        .into_iter()
+        .map(|f| f.name)
+        .collect();
+    assert!(names.is_empty(), "unused: {:?}", names);
+}
+
+#[test]
+fn find_unused_functions_inline_preload_member_is_used() {
+    let (_dir, root) = project(&[
+        (
+            "x.gd",
+            "extends RefCounted\nconst MAX = 3\nconst UNUSED = 4\nstatic func build():\n    return 1\nstatic func dead():\n    pass\n",
+        ),
+        (
+            "main.gd",
+            "extends Node\nfunc _ready():\n    var thing = preload(\"res://x.gd\").build()\n    print(preload(\"res://x.gd\").MAX)\n",
+        ),
+    ]);
+    assert_eq!(unused_names_scoped(&root), vec!["x.gd:dead"]);
+    let options = ScanOptions {
+        consts: true,
+        ..ScanOptions::default()
+    };
+    let scan = scan_directory_with_options(&root, &mut None, &options);
+    let consts: Vec<_> = find_unused_consts(&scan)
+        .into_iter()
+        .map(|c| c.name)
+        .collect();
+    assert_eq!(consts, vec!["UNUSED"]);
+}
+
+#[test]
+fn find_unused_functions_static_method_value_is_used() {
+    let (_dir, root) = project(&[
+        (
+            "factory.gd",
+            "class_name Factory\nextends RefCounted\nstatic func build():\n    return 1\nfunc helper():\n    pass\n",
+        ),
+        (
+            "main.gd",
+            "extends Node\nfunc _ready():\n    var f = Factory.build\n    var g = Factory.helper\n    print(f.call(), g)\n",
+        ),
+    ]);
+    assert_eq!(
+        unused_names_scoped(&root),
+        vec!["factory.gd:helper"],
+        "only static functions of the named class count"
+    );
+}
+
+#[test]
+fn find_unused_functions_dispatch_dict_value_is_used() {
+    let (_dir, root) = project(&[(
+        "fsm.gd",
+        "extends Node\nenum State { IDLE }\nvar current = State.IDLE\nvar states = {State.IDLE: \"idle_tick\"}\nfunc _process(_delta):\n    call(states[current])\nfunc idle_tick():\n    pass\nfunc dead():\n    pass\n",
+    )]);
+    assert_eq!(unused_names_scoped(&root), vec!["fsm.gd:dead"]);
+}
+
+#[test]
+fn find_unused_functions_autoload_call_resolves_to_singleton_script() {
+    let (_dir, root) = project(&[
+        (
+            "project.godot",
+            "[autoload]\n\nAudioManager=\"*res://autoload/audio_manager.gd\"\n",
+        ),
+        (
+            "autoload/audio_manager.gd",
+            "extends Node\nfunc play_sfx(_name):\n    pass\n",
+        ),
+        (
+            "enemy.gd",
+            "extends Node\nfunc play_sfx(_name):\n    pass\n",
+        ),
+        (
+            "main.gd",
+            "extends Node\nfunc _ready():\n    AudioManager.play_sfx(\"x\")\n",
+        ),
+    ]);
+    let unused = |autoloads: bool| -> Vec<String> {
+        let options = ScanOptions {
+            autoloads,
+            ..ScanOptions::default()
+        };
+        let scan = scan_directory_with_options(&root, &mut None, &options);
+        find_unused_functions_with(&scan, &AnalysisOptions::default())
+            .into_iter()
+            .map(|f| f.file.file_name().unwrap().to_string_lossy().into_owned())
+            .collect()
+    };
+    assert!(
+        unused(false).is_empty(),
+        "by name, the call keeps both alive"
+    );
+    assert_eq!(unused(true), vec!["enemy.gd"]);
+}
+
+#[test]
+fn find_unused_functions_noref_line_is_not_a_reference() {
+    let (_dir, root) = project(&[(
+        "main.gd",
+        "extends Node\nfunc _ready():\n    foo() # gdcf-noref\n    bar() # TEMP\nfunc foo():\n    pass\nfunc bar():\n    pass\n",
+    )]);
+    assert_eq!(unused_names_scoped(&root), vec!["main.gd:foo"]);
+    let options = ScanOptions {
+        noref_pattern: Some("# TEMP".to_string()),
+        ..ScanOptions::default()
+    };
+    let scan = scan_directory_with_options(&root, &mut None, &options);
+    let names: Vec<_> = find_unused_functions_with(&scan, &AnalysisOptions::default())
+        .into_iter()
+        .map(|f| f.name)
+        .collect();
+    assert_eq!(names, vec!["bar"]);
+}
+
+#[test]
+fn find_unreachable_functions_reports_dead_call_chain() {
+    let (_dir, root) = project(&[
+        (
+            "main.gd",
+            "extends Node\nvar speed = base_speed()\nfunc _ready():\n    live()\nfunc live():\n    pass\nfunc base_speed():\n    return 1\nfunc a():\n    b()\nfunc b():\n    pass\n",
+        ),
+        (
+            "ui.tscn",
+            "[connection signal=\"pressed\" from=\"Button\" to=\".\" method=\"on_pressed\"]\n",
+        ),
+        ("ui.gd", "extends Node\nfunc on_pressed():\n    helper()\nfunc helper():\n    pass\n"),
+    ]);
+    let scan = scan_directory(&root, &mut None, None);
+    let names =
+        |fns: Vec<gdcf::FunctionDef>| -> Vec<String> { fns.into_iter().map(|f| f.name).collect() };
+    assert_eq!(
+        names(find_unused_functions_with(
+            &scan,
+            &AnalysisOptions::default()
+        )),
+        vec!["a"],
+        "b() has a reference, so the per-name check keeps it"
+    );
+    assert_eq!(
+        names(find_unreachable_functions(
+            &scan,
+            &AnalysisOptions::default()
+        )),
+        vec!["a", "b"]
+    );
+}
+
+#[test]
+fn find_unreachable_functions_reports_cycle_without_external_caller() {
+    let (_dir, root) = project(&[(
+        "main.gd",
+        "extends Node\nfunc _ready():\n    ping(3)\nfunc ping(n):\n    pong(n)\nfunc pong(n):\n    if n > 0:\n        ping(n - 1)\nfunc a():\n    b()\nfunc b():\n    a()\nfunc spin():\n    spin()\n",
+    )]);
+    let scan = scan_directory(&root, &mut None, None);
+    assert!(find_unused_functions_with(&scan, &AnalysisOptions::default()).is_empty());
+    let names: Vec<_> = find_unreachable_functions(&scan, &AnalysisOptions::default())
+        .into_iter()
+        .map(|f| f.name)
+        .collect();
+    assert_eq!(
+        names,
+        vec!["a", "b", "spin"],
+        "ping/pong is reached from _ready"
+    );
+}
+
+#[test]
+fn find_unused_functions_call_group_keeps_group_method() {
+    let (_dir, root) = project(&[
+        (
+            "enemy.gd",
+            "extends Node\nfunc take_damage(dmg):\n    pass\nfunc heal():\n    pass\n",
+        ),
+        (
+            "game.gd",
+            "extends Node\nfunc _ready():\n    get_tree().call_group(\"enemies\", \"take_damage\", 3)\n",
+        ),
+    ]);
+    let scan = scan_directory(&root, &mut None, None);
+    let names: Vec<_> = find_unused_functions_with(&scan, &AnalysisOptions::default())
+        .into_iter()
+        .map(|f| f.name)
+        .collect();
+    assert_eq!(names, vec!["heal"]);
+}
+
+#[test]
+fn find_unused_functions_member_value_counts_only_for_functions() {
+    let (_dir, root) = project(&[(
+        "main.gd",
+        "extends Node\nvar health = 3\nfunc _ready():\n    var cbs = [self._step, self._other]\n    var h = self.health\n    run(player.on_hit)\nfunc _step():\n    pass\nfunc on_hit():\n    pass\nfunc unused():\n    pass\nfunc run(cb):\n    cb.call()\n",
+    )]);
+    let scan = scan_directory(&root, &mut None, None);
+    let names: Vec<_> = find_unused_functions_with(&scan, &AnalysisOptions::default())
+        .into_iter()
+        .map(|f| f.name)
+        .collect();
+    assert_eq!(names, vec!["unused"]);
+    assert!(
+        !scan.references.contains_key("_other") && !scan.references.contains_key("health"),
+        "members that name no function are dropped"
+    );
+}
@@ -4,13 +4,24 @@ mod common;
 use common::project;
 
 use clap::Parser;
-use gdcf::cli::{run, Args};
+use gdcf::cli::{rerun_on_changes, run, run_with_io, run_with_output, Args, RunError};
+use gdcf::Category;
 
-fn run_cli(args: &[&str]) -> i32 {
-    let argv: Vec<&str> = std::iter::once("godot-dead-code")
+fn argv<'a>(args: &[&'a str]) -> Vec<&'a str> {
+    std::iter::once("godot-dead-code")
         .chain(args.iter().copied())
-        .collect();
-    run(Args::parse_from(argv))
+        .collect()
+}
+
+fn run_cli(args: &[&str]) -> i32 {
+    run_with_output(Args::parse_from(argv(args)), &mut std::io::stdout())
+}
+
+/// Run the CLI and capture what it writes to stdout.
+fn run_cli_output(args: &[&str]) -> (i32, String) {
+    let mut buf = Vec::new();
+    let code = run_with_output(Args::parse_from(argv(args)), &mut buf);
+    (code, String::from_utf8(buf).unwrap())
 }
 
 #[test]
@@ -20,6 +31,28 @@ fn cli_empty_project_exit_zero() {
     assert_eq!(code, 0);
 }
 
+#[test]
+fn cli_run_returns_errors_and_findings() {
+    let (_dir, root) = project(&[(
+        "main.gd",
+        "extends Node\nfunc _ready():\n    pass\nfunc never_called():\n    pass\n",
+    )]);
+    let file = root.join("main.gd");
+    match run(Args::parse_from(argv(&[file.to_str().unwrap()]))) {
+        Err(e @ RunError::NotADirectory(_)) => {
+            assert!(e.to_string().starts_with("not a directory: "), "{}", e);
+            assert_eq!(e.exit_code(), 2);
+        }
+        other => panic!("expected NotADirectory, got {:?}", other),
+    }
+    let outcome = run(Args::parse_from(argv(&["-q", root.to_str().unwrap()]))).unwrap();
+    assert_eq!(outcome.exit_code, 1);
+    assert_eq!(outcome.findings.len(), 1);
+    assert_eq!(outcome.findings[0].category, Category::Unused);
+    assert_eq!(outcome.findings[0].name, "never_called");
+    assert_eq!(outcome.findings[0].file, std::path::Path::new("main.gd"));
+}
+
 #[test]
 fn cli_unused_function_exit_one() {
     let (_dir, root) = project(&[(
@@ -57,6 +90,37 @@ func unused():
     assert_eq!(code, 1);
 }
 
+#[test]
+fn cli_quiet_if_clean() {
+    let (_dir, root) = project(&[(
+        "main.gd",
+        "extends Node
+func _ready():
+    pass
+",
+    )]);
+    let (code, out) = run_cli_output(&["--quiet-if-clean", root.to_str().unwrap()]);
+    assert_eq!(code, 0);
+    assert!(
+        out.is_empty(),
+        "clean project should print nothing:\n{}",
+        out
+    );
+
+    std::fs::write(
+        root.join("main.gd"),
+        "extends Node\nfunc _ready():\n    pass\nfunc unused():\n    pass\n",
+    )
+    .unwrap();
+    let (code, out) = run_cli_output(&["--quiet-if-clean", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(
+        out.contains("unused"),
+        "findings should be reported:\n{}",
+        out
+    );
+}
+
 #[test]
 fn cli_verbose_summary() {
     let (_dir, root) = project(&[("main.gd", "extends Node\nfunc _ready():\n    pass\n")]);
@@ -131,6 +195,53 @@ fn cli_no_default_excludes() {
     );
 }
 
+#[test]
+fn cli_no_default_excludes_still_honors_user_excludes() {
+    let (_dir, root) = project(&[
+        ("main.gd", "extends Node\nfunc _ready(): pass\n"),
+        ("addons/plugin.gd", "func addon_dead(): pass\n"),
+        ("addons/generated/gen.gd", "func generated_dead(): pass\n"),
+        ("vendor/lib.gd", "func vendor_dead(): pass\n"),
+        (".godot/cache.gd", "func cache_dead(): pass\n"),
+        (".gdcfignore", "addons/generated/\n"),
+        ("gdcf.toml", "exclude_dirs = [\"vendor\"]\n"),
+    ]);
+    let (code, out) = run_cli_output(&["--no-default-excludes", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(out.contains("addon_dead"), "addons is scanned:\n{}", out);
+    assert!(
+        !out.contains("generated_dead"),
+        ".gdcfignore honored:\n{}",
+        out
+    );
+    assert!(
+        !out.contains("vendor_dead"),
+        "config excludes honored:\n{}",
+        out
+    );
+}
+
+#[test]
+fn cli_default_excludes_skip_dot_godot() {
+    let (_dir, root) = project(&[
+        ("main.gd", "extends Node\nfunc _ready(): pass\n"),
+        (".godot/editor/cache.gd", "func cache_dead(): pass\n"),
+    ]);
+    assert_eq!(run_cli(&[root.to_str().unwrap()]), 0);
+    // Hidden directories are skipped on their own too.
+    assert_eq!(
+        run_cli(&["--no-default-excludes", root.to_str().unwrap()]),
+        0
+    );
+    let (code, out) = run_cli_output(&[
+        "--no-default-excludes",
+        "--include-hidden",
+        root.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 1);
+    assert!(out.contains("cache_dead"), "output:\n{}", out);
+}
+
 #[test]
 fn cli_tests_dir_only_test_referenced() {
     let (_dir, root) = project(&[
@@ -157,3 +268,1564 @@ func test_thing():
         "only_called_from_test only referenced from test code"
     );
 }
+
+#[test]
+fn cli_format_sarif() {
+    let (_dir, root) = project(&[
+        (
+            "src/main.gd",
+            r#"extends Node
+func _ready():
+    pass
+func never_called():
+    pass
+func only_called_from_test():
+    pass
+"#,
+        ),
+        (
+            "tests/test_main.gd",
+            "extends Node\nfunc test_thing():\n    only_called_from_test()\n",
+        ),
+    ]);
+    let (code, out) = run_cli_output(&["--format", "sarif", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    let log: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(log["version"], "2.1.0");
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "godot-dead-code");
+    assert_eq!(run["tool"]["driver"]["version"], env!("CARGO_PKG_VERSION"));
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["ruleId"], "unused-function");
+    assert_eq!(results[0]["level"], "error");
+    let location = &results[0]["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "src/main.gd");
+    assert_eq!(location["region"]["startLine"], 4);
+    assert_eq!(results[1]["ruleId"], "test-only-function");
+    assert_eq!(results[1]["level"], "warning");
+}
+
+#[test]
+fn cli_format_sarif_clean_project() {
+    let (_dir, root) = project(&[("main.gd", "extends Node\nfunc _ready():\n    pass\n")]);
+    let (code, out) = run_cli_output(&["--format", "sarif", root.to_str().unwrap()]);
+    assert_eq!(code, 0);
+    let log: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert!(log["runs"][0]["results"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn cli_cache_dir_written() {
+    let (_dir, root) = project(&[("main.gd", "extends Node\nfunc _ready():\n    pass\n")]);
+    let cache = tempfile::tempdir().unwrap();
+    let cache_dir = cache.path().join("gdcf");
+    let args = [
+        "--cache-dir",
+        cache_dir.to_str().unwrap(),
+        root.to_str().unwrap(),
+    ];
+    assert_eq!(run_cli(&args), 0);
+    assert!(cache_dir.join("scan-cache.json").is_file());
+    assert_eq!(run_cli(&args), 0);
+}
+
+#[test]
+fn cli_findings_limit_per_name() {
+    let stub = "extends Node\nfunc generated_stub():\n    pass\n";
+    let (_dir, root) = project(&[
+        ("a.gd", stub),
+        ("b.gd", stub),
+        ("c.gd", stub),
+        ("main.gd", "extends Node\nfunc lonely():\n    pass\n"),
+    ]);
+    let (code, out) = run_cli_output(&["--findings-limit-per-name", "1", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    let stub_lines: Vec<_> = out
+        .lines()
+        .filter(|l| l.contains("generated_stub"))
+        .collect();
+    assert_eq!(stub_lines.len(), 1, "output:\n{}", out);
+    assert!(stub_lines[0].ends_with("generated_stub (+2 more)"));
+    let lonely: Vec<_> = out.lines().filter(|l| l.contains("lonely")).collect();
+    assert_eq!(lonely.len(), 1);
+    assert!(lonely[0].ends_with(": lonely"));
+}
+
+#[test]
+fn cli_findings_limit_per_name_rejects_zero() {
+    let argv = ["godot-dead-code", "--findings-limit-per-name", "0", "."];
+    assert!(Args::try_parse_from(argv).is_err());
+}
+
+#[test]
+fn cli_scoped_reports_cross_file_namesake() {
+    let (_dir, root) = project(&[
+        (
+            "a.gd",
+            "extends Node\nfunc _ready():\n    _helper()\nfunc _helper():\n    pass\n",
+        ),
+        ("b.gd", "extends Node\nfunc _helper():\n    pass\n"),
+    ]);
+    assert_eq!(run_cli(&[root.to_str().unwrap()]), 0);
+    let (code, out) = run_cli_output(&["--scoped", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(out.contains("b.gd:2: _helper"), "output:\n{}", out);
+    assert!(!out.contains("a.gd:4: _helper"));
+}
+
+#[test]
+fn cli_scoped_bare_test_call_is_not_also_test_only() {
+    let (_dir, root) = project(&[
+        (
+            "main.gd",
+            "extends Node\nfunc _ready():\n    pass\nfunc helper():\n    pass\n",
+        ),
+        (
+            "tests/test_main.gd",
+            "extends Node\nfunc test_it():\n    helper()\n",
+        ),
+    ]);
+    let (code, out) = run_cli_output(&["--scoped", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(out.contains("main.gd:4: helper"), "output:\n{}", out);
+    assert!(
+        !out.contains("Only called from test code"),
+        "reported twice:\n{}",
+        out
+    );
+    assert!(
+        out.contains("Found 1 unused, 0 test-only"),
+        "output:\n{}",
+        out
+    );
+}
+
+#[test]
+fn cli_explain_unused_lists_near_miss() {
+    let (_dir, root) = project(&[(
+        "main.gd",
+        r#"extends Node
+func _ready():
+    fooo()
+func foo():
+    pass
+"#,
+    )]);
+    let (code, out) = run_cli_output(&["--explain-unused", "foo", root.to_str().unwrap()]);
+    assert_eq!(code, 0);
+    assert!(out.contains("Explain unused: 'foo'"));
+    assert!(
+        out.contains("  Definitions: 1\n    main.gd:4: foo"),
+        "output:\n{}",
+        out
+    );
+    assert!(out.contains("References (excluding definition lines): 0"));
+    assert!(out.contains("Verdict: 'foo' has no references."));
+    assert!(out.contains("Near-miss references (edit distance 1): 1"));
+    assert!(out.contains("main.gd:3: fooo (no such function)"));
+}
+
+#[test]
+fn cli_explain_unused_referenced_function() {
+    let (_dir, root) = project(&[(
+        "main.gd",
+        "extends Node\nfunc _ready():\n    foo()\nfunc foo():\n    pass\n",
+    )]);
+    let (code, out) = run_cli_output(&["--explain-unused", "foo", root.to_str().unwrap()]);
+    assert_eq!(code, 0);
+    assert!(out.contains("References (excluding definition lines): 1\n    main.gd:3"));
+    assert!(out.contains("Verdict: 'foo' is referenced."));
+    assert!(out.contains("Near-miss references (edit distance 1): 0"));
+}
+
+#[test]
+fn cli_explain_states_verdict_and_reason() {
+    let (_dir, root) = project(&[
+        (
+            "main.gd",
+            "extends Node\nfunc _ready():\n    helper()\nfunc helper():\n    pass\nfunc dead():\n    pass\n",
+        ),
+        (
+            "tests/test_main.gd",
+            "extends Node\nfunc test_it():\n    tested()\n",
+        ),
+        ("lib.gd", "extends Node\nfunc tested():\n    pass\n"),
+    ]);
+    let root = root.to_str().unwrap();
+    let explain = |name: &str| {
+        let (code, out) = run_cli_output(&["--explain", name, root]);
+        assert_eq!(code, 0);
+        out
+    };
+    assert_eq!(
+        explain("_ready"),
+        "Explain: '_ready'\n  main.gd:2: used: engine callback\n"
+    );
+    assert_eq!(
+        explain("dead"),
+        "Explain: 'dead'\n  main.gd:6: reported unused: no references\n"
+    );
+    assert_eq!(
+        explain("helper"),
+        "Explain: 'helper'\n  main.gd:4: used: referenced\n    main.gd:3\n"
+    );
+    assert_eq!(
+        explain("tested"),
+        "Explain: 'tested'\n  lib.gd:2: reported test-only: only referenced from test code\n    tests/test_main.gd:3\n"
+    );
+    assert!(explain("missing").contains("No definition of 'missing' found."));
+}
+
+#[test]
+fn cli_signals_flag_reports_unused_signals() {
+    let (_dir, root) = project(&[(
+        "main.gd",
+        "extends Node\nsignal used\nsignal dead\nfunc _ready():\n    used.emit()\n",
+    )]);
+    let (code, out) = run_cli_output(&[root.to_str().unwrap()]);
+    assert_eq!(code, 0);
+    assert!(!out.contains("dead"));
+    let (code, out) = run_cli_output(&["--signals", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(out.contains("Unused signals (never emitted or connected):"));
+    assert!(out.contains("main.gd:3: dead"));
+    assert!(!out.contains(": used"));
+}
+
+#[test]
+fn cli_native_callback_not_reported() {
+    let (_dir, root) = project(&[(
+        "ext.gd",
+        "extends Node\nfunc _on_native_tick(dt):\n    pass\n",
+    )]);
+    let (code, out) = run_cli_output(&[root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(out.contains("_on_native_tick"));
+    let (code, out) = run_cli_output(&[
+        "--native-callback",
+        "_on_native_tick",
+        root.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 0);
+    assert!(out.contains("No unused functions found."));
+}
+
+#[test]
+fn cli_virtual_method_not_reported() {
+    let (_dir, root) = project(&[(
+        "enemy.gd",
+        "extends Node\nfunc _on_spawn():\n    pass\nfunc dead():\n    pass\n",
+    )]);
+    let (code, out) = run_cli_output(&["--virtual", "_on_spawn", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(!out.contains("_on_spawn"), "output:\n{}", out);
+    assert!(out.contains(": dead"));
+    std::fs::write(
+        root.join("gdcf.toml"),
+        "virtual_methods = [\"_on_spawn\"]\n",
+    )
+    .unwrap();
+    let (_, out) = run_cli_output(&[root.to_str().unwrap()]);
+    assert!(!out.contains("_on_spawn"), "config: {}", out);
+}
+
+#[test]
+fn cli_gdcfignore_allow_section_suppresses_function() {
+    let (_dir, root) = project(&[
+        (
+            "main.gd",
+            "extends Node\nfunc _ready():\n    pass\nfunc kept_for_later():\n    pass\n",
+        ),
+        (
+            ".gdcfignore",
+            "# project ignore file\n\n[allow]\n# wired up in the next milestone\nkept_for_later\n",
+        ),
+    ]);
+    let (code, out) = run_cli_output(&[root.to_str().unwrap()]);
+    assert_eq!(code, 0);
+    assert!(out.contains("No unused functions found."));
+}
+
+#[test]
+fn cli_gdcfignore_globs_skip_files_and_dirs() {
+    let (_dir, root) = project(&[
+        ("main.gd", "extends Node\nfunc _ready():\n    pass\n"),
+        ("generated/api.gd", "func gen_dead():\n    pass\n"),
+        ("legacy_old.gd", "func old_dead():\n    pass\n"),
+        ("src/live.gd", "func live_dead():\n    pass\n"),
+        (".gdcfignore", "generated/\n*_old.gd\n[allow]\n"),
+    ]);
+    let (code, out) = run_cli_output(&[root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(out.contains("live_dead"));
+    assert!(!out.contains("gen_dead"));
+    assert!(!out.contains("old_dead"));
+}
+
+#[test]
+fn cli_strip_prefix_removes_prefix_from_paths() {
+    let (_dir, root) = project(&[("game/main.gd", "func dead():\n    pass\n")]);
+    let root = root.canonicalize().unwrap();
+    let game = root.join("game");
+    let (code, out) = run_cli_output(&[
+        "--strip-prefix",
+        root.to_str().unwrap(),
+        game.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 1);
+    assert!(out.contains("\n  game/main.gd:1: dead"), "output:\n{}", out);
+    assert!(!out.contains(root.to_str().unwrap()));
+
+    let (_, out) = run_cli_output(&[
+        "--format",
+        "sarif",
+        "--strip-prefix",
+        root.to_str().unwrap(),
+        game.to_str().unwrap(),
+    ]);
+    assert!(
+        out.contains("\"uri\": \"game/main.gd\""),
+        "output:\n{}",
+        out
+    );
+}
+
+#[test]
+fn cli_config_file_at_root_is_applied() {
+    let (_dir, root) = project(&[
+        ("main.gd", "extends Node\nfunc _ready():\n    pass\n"),
+        ("vendor/lib.gd", "func vendor_dead():\n    pass\n"),
+        ("addons/plugin.gd", "func addon_dead():\n    pass\n"),
+        (
+            "gdcf.toml",
+            "exclude_dirs = [\"vendor\"]\nformat = \"sarif\"\n",
+        ),
+    ]);
+    let (code, out) = run_cli_output(&[root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(
+        out.contains("\"ruleId\""),
+        "config format not applied:\n{}",
+        out
+    );
+    assert!(!out.contains("vendor_dead"));
+    // Config exclude_dirs replace the default, like --exclude-dir does.
+    assert!(out.contains("addon_dead"));
+
+    let (_, out) = run_cli_output(&["--format", "text", root.to_str().unwrap()]);
+    assert!(
+        out.starts_with("Unused (never called):"),
+        "flag must win:\n{}",
+        out
+    );
+}
+
+#[test]
+fn cli_no_flags_turn_off_config_booleans() {
+    let (_dir, root) = project(&[
+        (
+            "main.gd",
+            "extends Node\nsignal closed\nfunc _ready():\n    pass\n",
+        ),
+        ("addons/plugin.gd", "func addon_dead():\n    pass\n"),
+        (
+            "gdcf.toml",
+            "quiet = true\nsignals = true\nno_default_excludes = true\n",
+        ),
+    ]);
+    let root = root.to_str().unwrap();
+    let (code, out) = run_cli_output(&[root]);
+    assert_eq!(code, 1);
+    assert_eq!(out, "");
+    let (code, out) = run_cli_output(&["--no-quiet", root]);
+    assert_eq!(code, 1);
+    assert!(out.contains("closed"), "output:\n{}", out);
+    assert!(out.contains("addon_dead"), "output:\n{}", out);
+    let (code, out) = run_cli_output(&["--no-quiet", "--no-signals", "--default-excludes", root]);
+    assert_eq!(code, 0, "output:\n{}", out);
+    assert!(!out.contains("closed") && !out.contains("addon_dead"));
+    // The last of a flag and its `--no-` form wins.
+    let (_, out) = run_cli_output(&["--no-quiet", "--no-signals", "--signals", root]);
+    assert!(out.contains("closed"), "output:\n{}", out);
+}
+
+#[test]
+fn cli_config_without_exclude_dirs_keeps_default_addons_exclusion() {
+    let (_dir, root) = project(&[
+        (
+            "main.gd",
+            "extends Node\nfunc _ready():\n    pass\nfunc helper():\n    pass\n",
+        ),
+        ("addons/plugin.gd", "func addon_dead():\n    pass\n"),
+        ("spec/check.gd", "func check():\n    helper()\n"),
+        ("gdcf.toml", "test_dirs = [\"spec\"]\n"),
+    ]);
+    let (code, out) = run_cli_output(&[root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(!out.contains("addon_dead"), "output:\n{}", out);
+    assert!(
+        out.contains("Only called from test code (not from main app):\n  "),
+        "test_dirs from config not applied:\n{}",
+        out
+    );
+}
+
+#[test]
+fn cli_explicit_config_path_and_errors() {
+    let (_dir, root) = project(&[
+        ("main.gd", "extends Node\nfunc _ready():\n    pass\n"),
+        ("ci.toml", "quiet = true\n"),
+        ("bad.toml", "no_such_key = 1\n"),
+    ]);
+    let ci = root.join("ci.toml");
+    let (code, out) = run_cli_output(&["--config", ci.to_str().unwrap(), root.to_str().unwrap()]);
+    assert_eq!(code, 0);
+    assert!(out.is_empty(), "quiet from config:\n{}", out);
+    let bad = root.join("bad.toml");
+    assert_eq!(
+        run_cli(&["--config", bad.to_str().unwrap(), root.to_str().unwrap()]),
+        2
+    );
+    let missing = root.join("missing.toml");
+    assert_eq!(
+        run_cli(&[
+            "--config",
+            missing.to_str().unwrap(),
+            root.to_str().unwrap()
+        ]),
+        2
+    );
+}
+
+#[test]
+fn cli_baseline_created_when_missing() {
+    let (_dir, root) = project(&[("main.gd", "func old_dead():\n    pass\n")]);
+    let baseline = root.join("gdcf-baseline.json");
+    let (code, out) = run_cli_output(&[
+        "--baseline",
+        baseline.to_str().unwrap(),
+        root.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 0);
+    assert!(out.contains("No unused functions found."));
+    let text = std::fs::read_to_string(&baseline).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&text).unwrap();
+    let entries = json["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["name"], "old_dead");
+    assert_eq!(entries[0]["file"], "main.gd");
+    assert_eq!(entries[0]["rule"], "unused-function");
+    assert!(entries[0].get("line").is_none());
+}
+
+#[test]
+fn cli_baseline_hashes_non_utf8_definition_lines() {
+    let (_dir, root) = project(&[("main.gd", "func old_dead():\n    pass\n")]);
+    // Windows-1252 comment: not valid UTF-8, decoded lossily like the scan does.
+    std::fs::write(
+        root.join("latin1.gd"),
+        b"# caf\xe9\nfunc old_dead():\n    pass\n",
+    )
+    .unwrap();
+    let baseline = root.join("gdcf-baseline.json");
+    let args = [
+        "--baseline",
+        baseline.to_str().unwrap(),
+        root.to_str().unwrap(),
+    ];
+    assert_eq!(run_cli(&args), 0);
+    let text = std::fs::read_to_string(&baseline).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&text).unwrap();
+    let entries = json["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(
+        entries[0]["hash"], entries[1]["hash"],
+        "same definition line, same hash:\n{}",
+        text
+    );
+    assert_eq!(run_cli(&args), 0);
+}
+
+#[test]
+fn cli_baseline_clean_run_survives_edits_above() {
+    let (_dir, root) = project(&[("main.gd", "func old_dead():\n    pass\n")]);
+    let baseline = root.join("gdcf-baseline.json");
+    let args = [
+        "--baseline",
+        baseline.to_str().unwrap(),
+        root.to_str().unwrap(),
+    ];
+    assert_eq!(run_cli(&args), 0);
+    std::fs::write(
+        root.join("main.gd"),
+        "extends Node\n\n# moved down\nfunc old_dead():\n    pass\n",
+    )
+    .unwrap();
+    let (code, out) = run_cli_output(&args);
+    assert_eq!(code, 0);
+    assert!(out.contains("No unused functions found."));
+}
+
+#[test]
+fn cli_baseline_reports_only_new_findings() {
+    let (_dir, root) = project(&[("main.gd", "func old_dead():\n    pass\n")]);
+    let baseline = root.join("gdcf-baseline.json");
+    let args = [
+        "--baseline",
+        baseline.to_str().unwrap(),
+        root.to_str().unwrap(),
+    ];
+    assert_eq!(run_cli(&args), 0);
+    std::fs::write(
+        root.join("main.gd"),
+        "func old_dead():\n    pass\nfunc new_dead():\n    pass\n",
+    )
+    .unwrap();
+    let (code, out) = run_cli_output(&args);
+    assert_eq!(code, 1);
+    assert!(out.contains("new_dead"));
+    assert!(!out.contains("old_dead"));
+}
+
+#[test]
+fn cli_baseline_prune_drops_deleted_functions() {
+    let (_dir, root) = project(&[(
+        "main.gd",
+        "func old_dead():\n    pass\nfunc kept_dead():\n    pass\n",
+    )]);
+    let baseline = root.join("gdcf-baseline.json");
+    let path = root.to_str().unwrap();
+    assert_eq!(
+        run_cli(&["--baseline", baseline.to_str().unwrap(), path]),
+        0
+    );
+    std::fs::write(root.join("main.gd"), "func kept_dead():\n    pass\n").unwrap();
+    let (code, _) = run_cli_output(&[
+        "--baseline",
+        baseline.to_str().unwrap(),
+        "--baseline-prune",
+        path,
+    ]);
+    assert_eq!(code, 0);
+    let json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&baseline).unwrap()).unwrap();
+    let names: Vec<_> = json["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["kept_dead"]);
+}
+
+#[test]
+fn cli_format_github_prints_annotations() {
+    let (_dir, root) = project(&[
+        (
+            "src/main.gd",
+            "extends Node\nfunc _ready():\n    pass\nfunc dead():\n    pass\nfunc helper():\n    pass\n",
+        ),
+        ("tests/test_main.gd", "func test_it():\n    helper()\n"),
+    ]);
+    let (code, out) = run_cli_output(&["--format", "github", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert_eq!(
+        out,
+        "::error file=src/main.gd,line=4::dead is never called\n\
+         ::warning file=src/main.gd,line=6::helper is only called from test code\n"
+    );
+}
+
+#[test]
+fn cli_format_junit_has_one_failure_per_finding() {
+    let (_dir, root) = project(&[
+        (
+            "src/main.gd",
+            "extends Node\nsignal never_emitted\nfunc _ready():\n    pass\nfunc dead():\n    pass\nfunc helper():\n    pass\n",
+        ),
+        ("tests/test_main.gd", "func test_it():\n    helper()\n"),
+    ]);
+    let root = root.to_str().unwrap();
+    let (code, out) = run_cli_output(&["--signals", "--format", "junit", root]);
+    assert_eq!(code, 1);
+    let doc = roxmltree::Document::parse(&out).unwrap();
+    let suites: Vec<_> = doc
+        .descendants()
+        .filter(|n| n.has_tag_name("testsuite"))
+        .map(|n| {
+            (
+                n.attribute("name").unwrap(),
+                n.attribute("failures").unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(suites, vec![("unused", "2"), ("test-only", "1")]);
+    let failures = doc
+        .descendants()
+        .filter(|n| n.has_tag_name("failure"))
+        .count();
+    let (_, json) = run_cli_output(&["--signals", "--format", "json", root]);
+    let report: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(failures, report["findings"].as_array().unwrap().len());
+    let dead = doc
+        .descendants()
+        .find(|n| n.has_tag_name("testcase") && n.attribute("name") == Some("dead"))
+        .unwrap();
+    assert_eq!(dead.attribute("file"), Some("src/main.gd"));
+    assert_eq!(dead.attribute("line"), Some("5"));
+}
+
+#[test]
+fn cli_tres_method_string_counts_unless_no_tres() {
+    let (_dir, root) = project(&[
+        (
+            "state.gd",
+            "extends Node\nfunc _on_enter():\n    pass\nfunc _on_exit():\n    pass\n",
+        ),
+        (
+            "states/idle.tres",
+            r#"[gd_resource type="Resource" script_class="State" load_steps=2 format=3]
+
+[ext_resource type="Script" path="res://state.gd" id="1"]
+
+[resource]
+script = ExtResource("1")
+method = "_on_enter"
+exit_callback = &"_on_exit"
+display_name = "Idle state"
+"#,
+        ),
+    ]);
+    let (code, out) = run_cli_output(&[root.to_str().unwrap()]);
+    assert_eq!(code, 0, "output:\n{}", out);
+    let (code, out) = run_cli_output(&["--no-tres", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(out.contains("_on_enter"));
+    assert!(out.contains("_on_exit"));
+}
+
+#[test]
+fn cli_skip_test_definitions_hides_dead_test_helpers() {
+    let (_dir, root) = project(&[
+        (
+            "main.gd",
+            "extends Node\nfunc _ready():\n    pass\nfunc helper():\n    pass\n",
+        ),
+        (
+            "tests/test_main.gd",
+            "func test_it():\n    helper()\nfunc dead_fixture():\n    pass\n",
+        ),
+    ]);
+    let (code, out) = run_cli_output(&[root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(out.contains("dead_fixture"));
+    let (code, out) = run_cli_output(&["--skip-test-definitions", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(!out.contains("dead_fixture"), "output:\n{}", out);
+    assert!(out.contains("Only called from test code (not from main app):"));
+    assert!(out.contains(": helper"));
+}
+
+#[test]
+fn cli_strict_tests_reports_unused_test_prefixed_helpers() {
+    let (_dir, root) = project(&[
+        (
+            "main.gd",
+            "extends Node\nfunc _ready():\n    pass\nfunc test_utils_build_fixture():\n    pass\nfunc before_each():\n    pass\n",
+        ),
+        ("tests/test_main.gd", "func test_it():\n    pass\n"),
+    ]);
+    let root = root.to_str().unwrap();
+    let (code, out) = run_cli_output(&[root]);
+    assert_eq!(code, 0, "output:\n{}", out);
+    let (code, out) = run_cli_output(&["--strict-tests", root]);
+    assert_eq!(code, 1);
+    assert!(
+        out.contains("main.gd:4: test_utils_build_fixture"),
+        "output:\n{}",
+        out
+    );
+    assert!(!out.contains("before_each"), "hooks stay exempt");
+    assert!(!out.contains("test_it"), "tests in test code stay exempt");
+}
+
+#[test]
+fn cli_exclude_unused_in_keeps_test_only_findings() {
+    let (_dir, root) = project(&[
+        ("main.gd", "extends Node\nfunc _ready():\n    pass\n"),
+        (
+            "vendor/lib.gd",
+            "func vendored_dead():\n    pass\nfunc vendored_tested():\n    pass\n",
+        ),
+        (
+            "tests/test_lib.gd",
+            "func test_it():\n    vendored_tested()\n",
+        ),
+    ]);
+    let (code, out) = run_cli_output(&[root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(out.contains("vendored_dead"));
+    let (code, out) = run_cli_output(&["--exclude-unused-in", "vendor/", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(!out.contains("vendored_dead"), "output:\n{}", out);
+    assert!(out.contains("Only called from test code (not from main app):"));
+    assert!(out.contains(": vendored_tested"));
+}
+
+#[test]
+fn cli_test_framework_selects_lifecycle_hooks() {
+    let (_dir, root) = project(&[
+        ("main.gd", "extends Node\nfunc _ready():\n    pass\n"),
+        (
+            "tests/test_player.gd",
+            "extends GdUnitTestSuite\nfunc before():\n    pass\nfunc after():\n    pass\nfunc test_jump():\n    pass\n",
+        ),
+    ]);
+    let path = root.to_str().unwrap();
+    let (code, out) = run_cli_output(&["--test-framework", "gdunit4", path]);
+    assert_eq!(code, 0, "output:\n{}", out);
+    let (code, _) = run_cli_output(&[path]);
+    assert_eq!(code, 0, "both (default) knows GdUnit4 hooks");
+    let (code, out) = run_cli_output(&["--test-framework", "gut", path]);
+    assert_eq!(code, 1);
+    assert!(out.contains(": before"), "output:\n{}", out);
+    assert!(out.contains(": after"));
+    assert!(!out.contains("test_jump"));
+}
+
+#[test]
+fn cli_sort_size_lists_largest_function_first() {
+    let long_body = "    pass\n".repeat(9);
+    let source = format!(
+        "extends Node\nfunc small():\n    pass\nfunc big():\n{}",
+        long_body
+    );
+    let (_dir, root) = project(&[("main.gd", source.as_str())]);
+    let (code, out) = run_cli_output(&[root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(out.find(": small").unwrap() < out.find(": big").unwrap());
+    let (code, out) = run_cli_output(&["--sort", "size", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(
+        out.find(": big").unwrap() < out.find(": small").unwrap(),
+        "output:\n{}",
+        out
+    );
+}
+
+#[test]
+fn cli_sort_keys_order_findings() {
+    let (_dir, root) = project(&[
+        (
+            "a.gd",
+            "extends Node\nfunc zeta():\n    pass\n\n\n\nfunc beta():\n    pass\n",
+        ),
+        ("b.gd", "extends Node\n\n\nfunc alpha():\n    pass\n"),
+    ]);
+    let names = |args: &[&str]| -> Vec<String> {
+        let mut all = args.to_vec();
+        all.push(root.to_str().unwrap());
+        let (code, out) = run_cli_output(&all);
+        assert_eq!(code, 1);
+        out.lines()
+            .filter_map(|line| line.strip_prefix("  "))
+            .map(|line| line.rsplit(": ").next().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(names(&[]), vec!["zeta", "beta", "alpha"]);
+    assert_eq!(names(&["--sort", "file"]), vec!["zeta", "beta", "alpha"]);
+    assert_eq!(names(&["--sort", "line"]), vec!["zeta", "alpha", "beta"]);
+    assert_eq!(names(&["--sort", "name"]), vec!["alpha", "beta", "zeta"]);
+    let (_, out) = run_cli_output(&["--sort", "name", "--format", "json", root.to_str().unwrap()]);
+    let alpha = out.find("\"alpha\"").unwrap();
+    let beta = out.find("\"beta\"").unwrap();
+    let zeta = out.find("\"zeta\"").unwrap();
+    assert!(alpha < beta && beta < zeta, "output:\n{}", out);
+}
+
+#[test]
+fn cli_prints_summary_line() {
+    let (_dir, root) = project(&[
+        (
+            "main.gd",
+            "extends Node\nfunc _ready():\n    pass\nfunc dead():\n    pass\nfunc helper():\n    pass\n",
+        ),
+        ("tests/test_main.gd", "func test_it():\n    helper()\n"),
+    ]);
+    let (code, out) = run_cli_output(&[root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(
+        out.ends_with(
+            "Found 1 unused, 1 test-only in 1 files (scanned 2 scripts, 4 defs, 5 refs)\n"
+        ),
+        "output:\n{}",
+        out
+    );
+    let (_dir, signals) = project(&[("hud.gd", "extends Node\nsignal closed\n")]);
+    let (code, out) = run_cli_output(&["--signals", signals.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(
+        out.contains("Found 1 unused, 0 test-only in 1 files (scanned 1 scripts,"),
+        "output:\n{}",
+        out
+    );
+    let (_, out) = run_cli_output(&["--format", "json", root.to_str().unwrap()]);
+    assert!(!out.contains("Found "));
+}
+
+#[test]
+fn cli_output_writes_report_to_file() {
+    let (_dir, root) = project(&[("main.gd", "extends Node\nfunc dead():\n    pass\n")]);
+    let target = root.join("reports/nested/out.json");
+    let (code, out) = run_cli_output(&[
+        "--format",
+        "json",
+        "--output",
+        target.to_str().unwrap(),
+        root.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 1);
+    assert!(out.is_empty(), "stdout: {}", out);
+    let report = gdcf::report::json::from_json(&std::fs::read_to_string(&target).unwrap()).unwrap();
+    assert_eq!(report.findings.len(), 1);
+    assert_eq!(report.findings[0].name, "dead");
+    assert_eq!(report.findings[0].file, std::path::Path::new("main.gd"));
+}
+
+#[test]
+fn cli_output_is_rewritten_in_quiet_modes() {
+    let (_dir, root) = project(&[("main.gd", "extends Node\nfunc _ready():\n    pass\n")]);
+    let target = root.join("out.json");
+    for quiet in ["--quiet", "--quiet-if-clean"] {
+        std::fs::write(&target, "stale report from an earlier run").unwrap();
+        let (code, out) = run_cli_output(&[
+            quiet,
+            "--format",
+            "json",
+            "--output",
+            target.to_str().unwrap(),
+            root.to_str().unwrap(),
+        ]);
+        assert_eq!(code, 0);
+        assert!(out.is_empty(), "stdout: {}", out);
+        let text = std::fs::read_to_string(&target).unwrap();
+        let report = gdcf::report::json::from_json(&text).unwrap();
+        assert!(report.findings.is_empty(), "{}: {}", quiet, text);
+    }
+}
+
+#[test]
+fn cli_output_per_file_mirrors_source_tree() {
+    let (_dir, root) = project(&[
+        (
+            "scripts/player.gd",
+            "extends Node\nfunc _ready():\n    pass\nfunc dead():\n    pass\n",
+        ),
+        (
+            "scripts/clean.gd",
+            "extends Node\nfunc _ready():\n    pass\n",
+        ),
+    ]);
+    let out_dir = tempfile::tempdir().unwrap();
+    let code = run_cli(&[
+        "--output-per-file",
+        out_dir.path().to_str().unwrap(),
+        root.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 1);
+    let player = out_dir.path().join("scripts/player.gd.json");
+    let report = gdcf::report::json::from_json(&std::fs::read_to_string(player).unwrap()).unwrap();
+    assert_eq!(report.findings.len(), 1);
+    assert_eq!(report.findings[0].name, "dead");
+    assert!(!out_dir.path().join("scripts/clean.gd.json").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn cli_output_per_file_never_writes_outside_dir() {
+    let (_dir, base) = project(&[
+        (
+            "game/main.gd",
+            "extends Node\nfunc _ready():\n    pass\nfunc dead():\n    pass\n",
+        ),
+        (
+            "shared/lib.gd",
+            "extends Node\nfunc shared_dead():\n    pass\n",
+        ),
+    ]);
+    let root = base.join("game");
+    std::os::unix::fs::symlink(base.join("shared"), root.join("shared")).unwrap();
+    let out_dir = base.join("out");
+    let code = run_cli(&[
+        "--follow-symlinks",
+        "--output-per-file",
+        out_dir.to_str().unwrap(),
+        root.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 1);
+    assert!(out_dir.join("main.gd.json").exists());
+    assert!(!base.join("shared/lib.gd.json").exists());
+    let written: Vec<_> = std::fs::read_dir(&out_dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name())
+        .collect();
+    assert_eq!(written, vec!["main.gd.json"]);
+}
+
+#[test]
+fn cli_fail_on_selects_failing_categories() {
+    let both = project(&[
+        (
+            "main.gd",
+            "extends Node\nfunc dead():\n    pass\nfunc helper():\n    pass\n",
+        ),
+        ("tests/test_main.gd", "func test_it():\n    helper()\n"),
+    ]);
+    let test_only = project(&[
+        ("main.gd", "extends Node\nfunc helper():\n    pass\n"),
+        ("tests/test_main.gd", "func test_it():\n    helper()\n"),
+    ]);
+    let code = |fail_on: &str, root: &std::path::Path| {
+        run_cli(&["--fail-on", fail_on, root.to_str().unwrap()])
+    };
+    assert_eq!(code("any", &both.1), 1);
+    assert_eq!(code("none", &both.1), 0);
+    assert_eq!(code("unused", &both.1), 1);
+    assert_eq!(code("test-only", &both.1), 1);
+    assert_eq!(code("any", &test_only.1), 1);
+    assert_eq!(code("unused", &test_only.1), 0);
+    assert_eq!(code("test-only", &test_only.1), 1);
+    let (code, out) = run_cli_output(&["--fail-on", "none", both.1.to_str().unwrap()]);
+    assert_eq!(code, 0);
+    assert!(out.contains(": dead"), "findings are still printed");
+}
+
+#[test]
+fn cli_severity_mapping_sets_exit_code() {
+    let (_dir, root) = project(&[
+        (
+            "main.gd",
+            "extends Node
+func helper():
+    pass
+",
+        ),
+        (
+            "tests/test_main.gd",
+            "func test_it():
+    helper()
+",
+        ),
+    ]);
+    let root = root.to_str().unwrap();
+    assert_eq!(
+        run_cli(&["--severity", "unused=error,test-only=warn", root]),
+        0
+    );
+    assert_eq!(run_cli(&["--severity", "test-only=error", root]), 1);
+    assert_eq!(
+        run_cli(&["--severity", "test-only=error,test-only=note", root]),
+        0,
+        "later entries win"
+    );
+    let (code, out) = run_cli_output(&["--severity", "test-only=note", "--format", "json", root]);
+    assert_eq!(code, 0);
+    let report: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(report["findings"][0]["severity"], "note");
+}
+
+#[test]
+fn cli_fail_on_keeps_default_severities() {
+    let (_dir, root) = project(&[
+        (
+            "main.gd",
+            "extends Node\nfunc dead():\n    pass\nfunc helper():\n    pass\n",
+        ),
+        ("tests/test_main.gd", "func test_it():\n    helper()\n"),
+    ]);
+    let levels = |fail_on: &str| {
+        let args = [
+            "--fail-on",
+            fail_on,
+            "--format",
+            "json",
+            root.to_str().unwrap(),
+        ];
+        let (code, out) = run_cli_output(&args);
+        let report: serde_json::Value = serde_json::from_str(&out).unwrap();
+        let levels: Vec<_> = report["findings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| {
+                (
+                    f["name"].as_str().unwrap().to_string(),
+                    f["severity"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect();
+        (code, levels)
+    };
+    let pair = |name: &str, level: &str| (name.to_string(), level.to_string());
+    let defaults = vec![pair("dead", "error"), pair("helper", "warning")];
+    assert_eq!(levels("any"), (1, defaults.clone()));
+    assert_eq!(levels("unused"), (1, defaults.clone()));
+    assert_eq!(levels("test-only"), (1, defaults.clone()));
+    assert_eq!(levels("none"), (0, defaults));
+}
+
+#[test]
+fn cli_severity_unused_covers_every_never_referenced_kind() {
+    let (_dir, root) = project(&[(
+        "main.gd",
+        "extends Node
+signal never_emitted
+func _ready():
+    pass
+",
+    )]);
+    let root = root.to_str().unwrap();
+    assert_eq!(
+        run_cli(&["--signals", "--severity", "unused=error", root]),
+        1
+    );
+    assert_eq!(
+        run_cli(&["--signals", "--severity", "unused=warn", root]),
+        0
+    );
+    assert_eq!(
+        run_cli(&["--signals", "--severity", "unused-signal=error", root]),
+        1
+    );
+}
+
+#[test]
+fn cli_severity_rejects_unknown_kinds_and_levels() {
+    for value in ["unused", "dead=error", "unused=fatal"] {
+        assert!(
+            Args::try_parse_from(["godot-dead-code", "--severity", value, "."]).is_err(),
+            "{} should be rejected",
+            value
+        );
+    }
+    assert!(Args::try_parse_from([
+        "godot-dead-code",
+        "--severity",
+        "unused=error",
+        "--fail-on",
+        "none",
+        "."
+    ])
+    .is_err());
+}
+
+#[test]
+fn cli_warn_dynamic_does_not_credit_lookup_key() {
+    let (_dir, root) = project(&[(
+        "main.gd",
+        "extends Node\nvar data = {}\nfunc handler():\n    pass\nfunc _ready():\n    data.get(\"handler\").call()\n",
+    )]);
+    let (code, out) = run_cli_output(&["--warn-dynamic", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(
+        out.contains(": handler"),
+        "the key does not keep handler alive"
+    );
+}
+
+#[test]
+fn cli_test_dir_filename_glob() {
+    let (_dir, root) = project(&[
+        ("main.gd", "extends Node\nfunc helper():\n    pass\n"),
+        (
+            "src/player_spec.gd",
+            "extends Node\nfunc it_works():\n    helper()\n",
+        ),
+    ]);
+    let (code, out) = run_cli_output(&["--test-dir", "**/*_spec.gd", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(
+        out.contains("Only called from test code") && out.contains(": helper"),
+        "helper is only called from a *_spec.gd file:\n{}",
+        out
+    );
+}
+
+#[test]
+fn cli_show_filters_sections_but_not_exit_code() {
+    let (_dir, root) = project(&[(
+        "main.gd",
+        "extends Node\nsignal never_emitted\nfunc dead():\n    pass\n",
+    )]);
+    let root = root.to_str().unwrap();
+    let (code, out) = run_cli_output(&["--signals", "--show", "signals", root]);
+    assert_eq!(code, 1);
+    assert!(out.contains("Unused signals"), "{}", out);
+    assert!(out.contains(": never_emitted"));
+    assert!(!out.contains("Unused (never called)"), "{}", out);
+    assert!(!out.contains(": dead"));
+    assert!(!out.contains("No unused functions found."));
+    // Hidden sections still fail the run.
+    let (_dir, clean_signals) = project(&[(
+        "main.gd",
+        "extends Node\nsignal hit\nfunc dead():\n    hit.emit()\n",
+    )]);
+    let (code, out) = run_cli_output(&[
+        "--signals",
+        "--show",
+        "signals",
+        clean_signals.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 1, "dead() is hidden but still counts:\n{}", out);
+}
+
+#[test]
+fn cli_project_file_outside_root() {
+    let (_dir, root) = project(&[
+        (
+            "game/project.godot",
+            "[autoload]\nAudio=\"*res://audio.gd\"\n",
+        ),
+        ("game/audio.gd", "extends Node\nfunc play():\n    pass\n"),
+        ("game/enemy.gd", "extends Node\nfunc play():\n    pass\n"),
+        (
+            "game/main.gd",
+            "extends Node\nfunc _ready():\n    Audio.play()\n",
+        ),
+    ]);
+    let project_file = root.join("game/project.godot");
+    let args = [
+        "--autoloads",
+        "--project",
+        project_file.to_str().unwrap(),
+        root.to_str().unwrap(),
+    ];
+    let (code, out) = run_cli_output(&args);
+    assert_eq!(code, 1);
+    assert!(out.contains("enemy.gd:2: play"), "{}", out);
+    assert!(!out.contains("audio.gd"), "{}", out);
+    let missing = root.join("missing.godot");
+    let code = run_cli(&[
+        "--project",
+        missing.to_str().unwrap(),
+        root.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 2);
+}
+
+#[test]
+fn cli_changed_path_narrows_report_not_references() {
+    let (_dir, root) = project(&[
+        (
+            "src/player/player.gd",
+            "extends Node\nfunc jump():\n    pass\nfunc dead_in_player():\n    pass\n",
+        ),
+        (
+            "src/enemy/enemy.gd",
+            "extends Node\nfunc _ready():\n    get_parent().jump()\nfunc dead_in_enemy():\n    pass\n",
+        ),
+    ]);
+    let (code, out) = run_cli_output(&["--changed-path", "src/player", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(out.contains(": dead_in_player"), "{}", out);
+    assert!(
+        !out.contains("dead_in_enemy"),
+        "outside the changed path:\n{}",
+        out
+    );
+    assert!(
+        !out.contains(": jump"),
+        "called from outside the changed path:\n{}",
+        out
+    );
+    let (code, _) = run_cli_output(&["--changed-path", "src/enemy/", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+}
+
+#[test]
+fn cli_stdin_files_limits_report_to_listed_scripts() {
+    let (_dir, root) = project(&[
+        (
+            "player.gd",
+            "extends Node\nfunc jump():\n    pass\nfunc dead_in_player():\n    pass\n",
+        ),
+        (
+            "enemy.gd",
+            "extends Node\nfunc _ready():\n    get_parent().jump()\nfunc dead_in_enemy():\n    pass\n",
+        ),
+        ("level.tscn", "[gd_scene format=3]\n"),
+    ]);
+    let run_stdin = |list: &str| {
+        let mut out = Vec::new();
+        let args = Args::parse_from(argv(&["--stdin-files", root.to_str().unwrap()]));
+        let code = run_with_io(args, &mut list.as_bytes(), &mut out);
+        (code, String::from_utf8(out).unwrap())
+    };
+    let (code, out) = run_stdin("player.gd\nlevel.tscn\n\n");
+    assert_eq!(code, 1);
+    assert!(out.contains(": dead_in_player"), "{}", out);
+    assert!(!out.contains("dead_in_enemy"), "{}", out);
+    assert!(!out.contains(": jump"), "{}", out);
+    let (code, out) = run_stdin("level.tscn\nREADME.md\n");
+    assert_eq!(code, 0, "no listed scripts, nothing reported:\n{}", out);
+}
+
+#[test]
+fn cli_include_hidden_scans_dot_directories() {
+    let (_dir, root) = project(&[
+        ("main.gd", "extends Node\nfunc _ready():\n    pass\n"),
+        (
+            ".godot/foo.gd",
+            "extends Node\nfunc cached_stub():\n    pass\n",
+        ),
+        (
+            ".tools/gen.gd",
+            "extends Node\nfunc tool_helper():\n    pass\n",
+        ),
+    ]);
+    let root = root.to_str().unwrap();
+    let (code, out) = run_cli_output(&[root]);
+    assert_eq!(
+        code, 0,
+        "hidden directories are skipped by default:\n{}",
+        out
+    );
+    let (code, out) = run_cli_output(&["--include-hidden", root]);
+    assert_eq!(code, 1);
+    assert!(out.contains(": tool_helper"), "{}", out);
+    assert!(
+        !out.contains("cached_stub"),
+        ".godot is a default exclude:\n{}",
+        out
+    );
+    let (_, out) = run_cli_output(&["--include-hidden", "--no-default-excludes", root]);
+    assert!(out.contains(": cached_stub"), "{}", out);
+}
+
+#[test]
+fn cli_default_cache_dir_and_cache_flags() {
+    let (_dir, root) = project(&[("main.gd", "extends Node\nfunc _ready():\n    pass\n")]);
+    let cache_file = root.join(".gdcf-cache/scan-cache.json");
+    let root = root.to_str().unwrap();
+    assert_eq!(run_cli(&["--no-cache", root]), 0);
+    assert!(!cache_file.exists(), "--no-cache writes nothing");
+    assert_eq!(run_cli(&[root]), 0);
+    assert!(cache_file.exists(), "cached under the root by default");
+    std::fs::write(&cache_file, "stale").unwrap();
+    assert_eq!(run_cli(&["--clear-cache", "--no-cache", root]), 0);
+    assert!(!cache_file.exists());
+}
+
+#[test]
+fn rerun_on_changes_batches_relevant_events() {
+    use notify::event::{AccessKind, CreateKind, EventKind, ModifyKind};
+    use std::sync::mpsc;
+    let event = |kind: EventKind, path: &str| {
+        Ok(notify::Event::new(kind).add_path(std::path::PathBuf::from(path)))
+    };
+    let modify = EventKind::Modify(ModifyKind::Any);
+    let (tx, rx) = mpsc::channel();
+    // Ignored: a read, a non-script file and a script the filter rejects (e.g. under addons/).
+    tx.send(event(EventKind::Access(AccessKind::Any), "/game/main.gd"))
+        .unwrap();
+    tx.send(event(
+        EventKind::Create(CreateKind::File),
+        "/game/notes.txt",
+    ))
+    .unwrap();
+    tx.send(event(modify, "/game/addons/plugin.gd")).unwrap();
+    // One batch: the write and the events right behind it.
+    tx.send(event(modify, "/game/main.gd")).unwrap();
+    tx.send(event(modify, "/game/main.gd")).unwrap();
+    let is_watched = |path: &std::path::Path| {
+        path.extension().is_some_and(|ext| ext == "gd") && !path.starts_with("/game/addons")
+    };
+    let mut runs = 0;
+    rerun_on_changes(&rx, &is_watched, std::time::Duration::ZERO, &mut || {
+        runs += 1;
+        if runs == 1 {
+            tx.send(event(modify, "/game/addons/plugin.gd")).unwrap();
+            tx.send(event(modify, "/game/level.gd")).unwrap();
+        }
+        runs < 2
+    })
+    .unwrap();
+    assert_eq!(runs, 2);
+}
+
+#[test]
+fn cli_ext_adds_to_gd_files() {
+    let (_dir, root) = project(&[
+        ("main.gd", "extends Node\nfunc dead_gd():\n    pass\n"),
+        (
+            "tool.gdscript",
+            "extends Node\nfunc dead_tool():\n    pass\n",
+        ),
+    ]);
+    let (code, out) = run_cli_output(&["--ext", "gdscript", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(out.contains("dead_gd"), "output:\n{}", out);
+    assert!(out.contains("dead_tool"), "output:\n{}", out);
+}
+
+#[test]
+fn cli_explain_config_shows_provenance() {
+    let (_dir, root) = project(&[
+        ("main.gd", "extends Node\n"),
+        ("gdcf.toml", "test_dirs = [\"spec\"]\n"),
+    ]);
+    let (code, out) = run_cli_output(&[
+        "--explain-config",
+        "--exclude-dir",
+        "vendor",
+        root.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 0);
+    assert!(out.starts_with("Config file: "), "output:\n{}", out);
+    assert!(out.contains("gdcf.toml\n"));
+    assert!(
+        out.contains("  exclude_dirs = [\"vendor\"] (cli)\n"),
+        "output:\n{}",
+        out
+    );
+    assert!(out.contains("  test_dirs = [\"spec\"] (config-file)\n"));
+    assert!(out.contains("  format = text (default)\n"));
+    assert!(out.contains("  quiet = false (default)\n"));
+}
+
+#[test]
+fn cli_input_action_prefix_keeps_action_handlers() {
+    let (_dir, root) = project(&[
+        (
+            "project.godot",
+            "config_version=5\n\n[input]\n\njump={\n\"deadzone\": 0.5,\n\"events\": []\n}\n",
+        ),
+        (
+            "player.gd",
+            "extends Node\nfunc _on_action_jump():\n    pass\nfunc _on_action_dash():\n    pass\n",
+        ),
+    ]);
+    let (code, out) = run_cli_output(&[root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(out.contains("_on_action_jump"));
+    let (code, out) = run_cli_output(&[
+        "--input-action-prefix",
+        "_on_action_",
+        root.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 1);
+    assert!(!out.contains("_on_action_jump"), "output:\n{}", out);
+    assert!(out.contains("_on_action_dash"));
+}
+
+#[test]
+fn cli_doc_refs_keep_functions_called_in_code_fences() {
+    let (_dir, root) = project(&[
+        (
+            "api.gd",
+            "extends Node\nfunc spawn_wave():\n    pass\nfunc unused_helper():\n    pass\n",
+        ),
+        (
+            "docs/api.md",
+            "# API\n\n```gdscript\nApi.spawn_wave()\n```\n\n```python\nunused_helper()\n```\n",
+        ),
+    ]);
+    let root = root.to_str().unwrap();
+    let (code, out) = run_cli_output(&[root]);
+    assert_eq!(code, 1);
+    assert!(out.contains("spawn_wave"), "off by default:\n{}", out);
+    let (code, out) = run_cli_output(&["--doc-refs", "docs/*.md", root]);
+    assert_eq!(code, 1);
+    assert!(!out.contains("spawn_wave"), "output:\n{}", out);
+    assert!(
+        out.contains("unused_helper"),
+        "non-GDScript fences are ignored"
+    );
+}
+
+#[test]
+fn cli_min_confidence_exact() {
+    let (_dir, root) = project(&[(
+        "main.gd",
+        "extends Node\nfunc _ready():\n    register(on_tick)\nfunc on_tick():\n    pass\n",
+    )]);
+    assert_eq!(run_cli(&[root.to_str().unwrap()]), 0);
+    let (code, out) = run_cli_output(&["--min-confidence", "exact", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(out.contains("on_tick"), "output:\n{}", out);
+}
+
+#[test]
+fn cli_min_confidence_applies_to_test_only() {
+    let (_dir, root) = project(&[
+        (
+            "main.gd",
+            "extends Node\nfunc _ready():\n    pass\nfunc helper():\n    pass\n",
+        ),
+        (
+            "tests/test_main.gd",
+            "func test_it():\n    var f = helper\n",
+        ),
+    ]);
+    let root = root.to_str().unwrap();
+    let (_, out) = run_cli_output(&[root]);
+    assert!(
+        out.contains("Found 0 unused, 1 test-only"),
+        "output:\n{}",
+        out
+    );
+    let (code, out) = run_cli_output(&["--min-confidence", "exact", root]);
+    assert_eq!(code, 1);
+    assert!(
+        out.contains("Found 1 unused, 0 test-only"),
+        "output:\n{}",
+        out
+    );
+}
+
+#[test]
+fn cli_with_signature_prints_func_line() {
+    let (_dir, root) = project(&[(
+        "main.gd",
+        "extends Node\nfunc _ready():\n    pass\n\tfunc unused_helper(a: int, b := 2) -> void:\n\t\tpass\n",
+    )]);
+    let (code, out) = run_cli_output(&["--with-signature", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(
+        out.contains(":4: unused_helper\n      func unused_helper(a: int, b := 2) -> void:\n"),
+        "output:\n{}",
+        out
+    );
+    let (_, out) = run_cli_output(&[root.to_str().unwrap()]);
+    assert!(!out.contains("func unused_helper"));
+}
+
+#[test]
+fn cli_group_by_file_prints_file_headers() {
+    let (_dir, root) = project(&[
+        (
+            "a.gd",
+            "extends Node\nfunc a_late():\n    pass\nfunc a_early():\n    pass\n",
+        ),
+        (
+            "b.gd",
+            "extends Node\nfunc b_dead():\n    pass\nfunc b_tested():\n    pass\n",
+        ),
+        ("tests/test_b.gd", "func test_b():\n    b_tested()\n"),
+    ]);
+    let path = root.to_str().unwrap();
+    let (code, out) = run_cli_output(&["--group-by-file", "--strip-prefix", path, path]);
+    assert_eq!(code, 1);
+    assert_eq!(
+        out,
+        "Unused (never called):\n  a.gd:\n    2: a_late\n    4: a_early\n  b.gd:\n    2: b_dead\n\
+         Only called from test code (not from main app):\n  b.gd:\n    4: b_tested\n\
+         Found 3 unused, 1 test-only in 2 files (scanned 3 scripts, 5 defs, 6 refs)\n"
+    );
+}
+
+#[test]
+fn cli_duplicates_lists_groups() {
+    let (_dir, root) = project(&[
+        ("a.gd", "extends Node\nfunc shared():\n    pass\n"),
+        ("sub/b.gd", "extends Node\nfunc shared():\n    pass\n"),
+    ]);
+    let (code, out) = run_cli_output(&["--duplicates", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert_eq!(
+        out,
+        "Duplicate definitions (same name in several files):\n  shared\n    a.gd:2\n    sub/b.gd:2\n"
+    );
+}
+
+#[test]
+fn cli_tscn_ref_property_keeps_method_used() {
+    let (_dir, root) = project(&[
+        (
+            "turret.gd",
+            "extends Node2D\n@export var callback_name := \"\"\nfunc _ready():\n    call(callback_name)\nfunc fire():\n    pass\n",
+        ),
+        (
+            "turret.tscn",
+            "[gd_scene format=3]\n\n[node name=\"Turret\" type=\"Node2D\"]\ncallback_name = \"fire\"\n",
+        ),
+    ]);
+    let (code, out) = run_cli_output(&[root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(out.contains(": fire"), "output:\n{}", out);
+    let code = run_cli(&[
+        "--tscn-ref-property",
+        "callback_name",
+        root.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 0);
+}
+
+#[test]
+fn cli_consts_reports_unused_constant() {
+    let (_dir, root) = project(&[(
+        "main.gd",
+        "extends Node\nconst USED = 1\nconst DEAD = 2\nfunc _ready():\n    print(USED)\n",
+    )]);
+    assert_eq!(run_cli(&[root.to_str().unwrap()]), 0);
+    let (code, out) = run_cli_output(&["--consts", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(
+        out.contains("Unused constants (never referenced):\n  ")
+            && out.contains("main.gd:3: DEAD\n"),
+        "output:\n{}",
+        out
+    );
+    assert!(!out.contains(": USED"));
+}
+
+#[test]
+fn cli_compare_json_reports() {
+    let (_dir, root) = project(&[(
+        "main.gd",
+        "extends Node\nfunc _ready():\n    pass\nfunc old_dead():\n    pass\n",
+    )]);
+    let reports = tempfile::tempdir().unwrap();
+    let old = reports.path().join("old.json");
+    let new = reports.path().join("new.json");
+    let (code, out) = run_cli_output(&["--format", "json", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(out.contains("\"category\": \"unused-function\""), "{}", out);
+    std::fs::write(&old, out).unwrap();
+
+    std::fs::write(
+        root.join("main.gd"),
+        "extends Node\nfunc _ready():\n    pass\nfunc new_dead():\n    pass\n",
+    )
+    .unwrap();
+    let (_, out) = run_cli_output(&["--format", "json", root.to_str().unwrap()]);
+    std::fs::write(&new, out).unwrap();
+
+    let (code, out) = run_cli_output(&["--compare", old.to_str().unwrap(), new.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert_eq!(
+        out,
+        "Added (1):\n  main.gd:4: new_dead is never called\nRemoved (1):\n  main.gd:4: old_dead is never called\n"
+    );
+    let (code, out) = run_cli_output(&["--compare", new.to_str().unwrap(), new.to_str().unwrap()]);
+    assert_eq!(code, 0);
+    assert_eq!(out, "Added (0):\nRemoved (0):\n");
+}
+
+#[test]
+fn cli_enums_reports_unused_member() {
+    let (_dir, root) = project(&[(
+        "main.gd",
+        "extends Node\nenum {\n    NORTH,\n    SOUTH,\n}\nfunc _ready():\n    print(NORTH)\n",
+    )]);
+    assert_eq!(run_cli(&[root.to_str().unwrap()]), 0);
+    let (code, out) = run_cli_output(&["--enums", root.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(
+        out.contains("Unused enum members (never referenced):\n  ")
+            && out.contains("main.gd:4: SOUTH\n"),
+        "output:\n{}",
+        out
+    );
+}
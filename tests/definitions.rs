@@ -31,3 +31,235 @@ fn find_function_definitions_static() {
     assert_eq!(defs[0].name, "helper");
     assert!(defs[0].is_static);
 }
+
+#[test]
+fn find_function_definitions_multiline_signature() {
+    let source = r#"extends Node
+
+func spawn(
+    pos: Vector2,
+    vel: Vector2
+) -> void:
+    pass
+
+func after():
+    pass
+"#;
+    let defs = find_function_definitions(Path::new("x.gd"), source);
+    let sites: Vec<_> = defs.iter().map(|d| (d.name.as_str(), d.line)).collect();
+    assert_eq!(sites, vec![("spawn", 3), ("after", 9)]);
+}
+
+#[test]
+fn find_function_definitions_nested_paren_default() {
+    let source = r##"func f(a = Vector2(1, 2), b := Color("#ff0000")) -> Array[int]:
+    return []
+static func g(opts = {"k": (1)}):
+    pass
+"##;
+    let defs = find_function_definitions(Path::new("x.gd"), source);
+    let names: Vec<_> = defs.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(names, vec!["f", "g"]);
+    assert!(!defs[0].is_static);
+    assert!(defs[1].is_static);
+    assert_eq!(defs[1].line, 3);
+}
+
+#[test]
+fn find_function_definitions_requires_colon() {
+    let source = "func not_a_def(a, b)\nfunc real():\n    pass\n";
+    let defs = find_function_definitions(Path::new("x.gd"), source);
+    let names: Vec<_> = defs.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(names, vec!["real"]);
+}
+
+#[test]
+fn find_function_definitions_column_of_name() {
+    let source =
+        "extends Node\n\nfunc _ready():\n    pass\n\n\tstatic func  never_called():\n    pass\n";
+    let defs = find_function_definitions(Path::new("main.gd"), source);
+    let fd = defs.iter().find(|d| d.name == "never_called").unwrap();
+    assert_eq!((fd.line, fd.column), (6, 15));
+    assert_eq!(defs[0].column, 6);
+}
+
+#[test]
+fn find_function_definitions_end_line_spans() {
+    let source = r#"extends Node
+
+func multi(a,
+        b):
+    var x = a
+    if x:
+        for i in b:
+            print(i)
+
+    # trailing comment inside the body
+    return x
+
+func one(): pass
+class Inner:
+    func nested():
+        pass
+        pass
+
+    func nested_one(): return 1
+var after = 1
+func last():
+    pass
+    pass"#;
+    let defs = find_function_definitions(Path::new("main.gd"), source);
+    let spans: Vec<_> = defs
+        .iter()
+        .map(|d| (d.name.as_str(), d.line, d.end_line))
+        .collect();
+    assert_eq!(
+        spans,
+        vec![
+            ("multi", 3, 11),
+            ("one", 13, 13),
+            ("nested", 15, 17),
+            ("nested_one", 19, 19),
+            ("last", 21, 23),
+        ]
+    );
+}
+
+#[test]
+fn find_function_definitions_end_line_with_multiline_strings() {
+    let source = r#"extends Node
+const HELP = """
+usage:
+  run it
+"""
+
+func describe():
+    var text = """
+first
+second
+"""
+    return text
+
+func after():
+    pass
+"#;
+    let defs = find_function_definitions(Path::new("help.gd"), source);
+    let spans: Vec<_> = defs
+        .iter()
+        .map(|d| (d.name.as_str(), d.line, d.end_line))
+        .collect();
+    assert_eq!(spans, vec![("describe", 7, 12), ("after", 14, 15)]);
+}
+
+#[test]
+fn find_function_definitions_rpc_annotation() {
+    let source = r#"extends Node
+
+@rpc
+func sync_position(pos):
+    pass
+
+@rpc("any_peer", "call_local")
+@warning_ignore("unused_parameter")
+func take_damage(amount):
+    pass
+
+@rpc_helper
+func not_rpc():
+    pass
+
+func plain():
+    pass
+"#;
+    let defs = find_function_definitions(Path::new("player.gd"), source);
+    let rpc: Vec<_> = defs.iter().map(|d| (d.name.as_str(), d.is_rpc)).collect();
+    assert_eq!(
+        rpc,
+        vec![
+            ("sync_position", true),
+            ("take_damage", true),
+            ("not_rpc", false),
+            ("plain", false),
+        ]
+    );
+}
+
+#[test]
+fn find_function_definitions_rpc_after_multiline_string() {
+    let source = "extends Node\nvar doc = \"\"\"\nline one\nline two\nline three\n\"\"\"\n\n@rpc\nfunc sync():\n    pass\n\nfunc helper():\n    pass\n";
+    let defs = find_function_definitions(Path::new("net.gd"), source);
+    let rpc: Vec<_> = defs
+        .iter()
+        .map(|d| (d.name.as_str(), d.line, d.is_rpc))
+        .collect();
+    assert_eq!(rpc, vec![("sync", 9, true), ("helper", 12, false)]);
+}
+
+#[test]
+fn find_function_definitions_inner_class_ownership() {
+    let source = r#"extends Node
+
+func reset():
+    pass
+
+class Pool:
+    extends RefCounted
+    var items = []
+
+    func reset():
+        items.clear()
+
+    class Slot extends RefCounted:
+        func reset():
+            pass
+
+    func _grow(
+        n: int,
+    ):
+        pass
+
+func after_classes():
+    pass
+"#;
+    let defs = find_function_definitions(Path::new("pool.gd"), source);
+    let owners: Vec<_> = defs
+        .iter()
+        .map(|d| (d.name.as_str(), d.line, d.inner_class.as_deref()))
+        .collect();
+    assert_eq!(
+        owners,
+        vec![
+            ("reset", 3, None),
+            ("reset", 10, Some("Pool")),
+            ("reset", 14, Some("Pool.Slot")),
+            ("_grow", 17, Some("Pool")),
+            ("after_classes", 22, None),
+        ]
+    );
+}
+
+#[test]
+fn find_function_definitions_inner_class_after_multiline_string() {
+    let source = r#"extends Node
+const BANNER = """
+class Fake:
+    func trap():
+"""
+
+class Real:
+    func inside():
+        pass
+
+func outside():
+    pass
+"#;
+    let defs = find_function_definitions(Path::new("banner.gd"), source);
+    let owners: Vec<_> = defs
+        .iter()
+        .map(|d| (d.name.as_str(), d.line, d.inner_class.as_deref()))
+        .collect();
+    assert_eq!(
+        owners,
+        vec![("inside", 8, Some("Real")), ("outside", 11, None)]
+    );
+}
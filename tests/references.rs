@@ -1,8 +1,13 @@
-//! Integration tests for find_function_references and find_tscn_references.
+//! Integration tests for find_function_references, find_tscn_references and find_tres_references.
 
+use std::collections::HashSet;
 use std::path::Path;
 
-use gdcf::scanner::{find_function_references, find_tscn_references};
+use gdcf::scanner::{
+    find_const_aliases, find_function_references, find_function_references_known, find_references,
+    find_tres_references, find_tscn_property_references, find_tscn_references, AliasTarget,
+    Confidence,
+};
 
 #[test]
 fn find_function_references_direct_call() {
@@ -33,6 +38,98 @@ func _on_button_pressed():
     assert!(names.contains(&"_on_button_pressed"));
 }
 
+#[test]
+fn find_function_references_connect_legacy() {
+    let source = r#"
+func _ready():
+    $Button.connect("pressed", self, "_on_pressed")
+    connect("tree_exited", other_node, '_on_tree_exited')
+
+func _on_pressed():
+    pass
+"#;
+    let refs = find_function_references(Path::new("a.gd"), source);
+    assert!(refs.contains(&("_on_pressed".to_string(), 3)));
+    assert!(refs.contains(&("_on_tree_exited".to_string(), 4)));
+}
+
+#[test]
+fn find_function_references_bind() {
+    let source = r#"
+func _ready():
+    register(1, _on_hit.bind(2))
+
+func _on_hit(amount):
+    pass
+"#;
+    let refs = find_function_references(Path::new("a.gd"), source);
+    assert!(refs.contains(&("_on_hit".to_string(), 3)));
+}
+
+#[test]
+fn find_function_references_tool_script_string_dispatch() {
+    let source = r#"@tool
+extends EditorPlugin
+
+func _enter_tree():
+    ClassDB.class_call_static("MyTools", "rebuild_cache")
+    callv("_refresh_preview", [])
+    add_tool_menu_item("Bake", self, "_on_bake_pressed")
+"#;
+    let refs = find_function_references(Path::new("plugin.gd"), source);
+    assert!(refs.contains(&("rebuild_cache".to_string(), 5)));
+    assert!(refs.contains(&("_refresh_preview".to_string(), 6)));
+    assert!(refs.contains(&("_on_bake_pressed".to_string(), 7)));
+}
+
+#[test]
+fn find_function_references_string_dispatch_requires_tool() {
+    let source = r#"extends Node
+
+func _ready():
+    callv("_refresh_preview", [])
+"#;
+    let refs = find_function_references(Path::new("a.gd"), source);
+    let names: Vec<_> = refs.iter().map(|r| r.0.as_str()).collect();
+    assert!(!names.contains(&"_refresh_preview"));
+}
+
+#[test]
+fn find_function_references_ignores_commented_call() {
+    let source = r#"
+func _ready():
+    # old_helper()
+    new_helper() # call("legacy_helper")
+"#;
+    let refs = find_function_references(Path::new("a.gd"), source);
+    let names: Vec<_> = refs.iter().map(|r| r.0.as_str()).collect();
+    assert!(!names.contains(&"old_helper"));
+    assert!(!names.contains(&"legacy_helper"));
+    assert!(refs.contains(&("new_helper".to_string(), 4)));
+}
+
+#[test]
+fn find_function_references_hash_inside_string_is_not_comment() {
+    let source = r##"
+func _ready():
+    var label = "#1 " + str(score()); call("after_hash")
+"##;
+    let refs = find_function_references(Path::new("a.gd"), source);
+    assert!(refs.contains(&("score".to_string(), 3)));
+    assert!(refs.contains(&("after_hash".to_string(), 3)));
+}
+
+#[test]
+fn find_function_references_has_method_string() {
+    let source = r#"
+func _apply(target, method_name):
+    if target.has_method("do_it"):
+        target.call(method_name)
+"#;
+    let refs = find_function_references(Path::new("a.gd"), source);
+    assert!(refs.contains(&("do_it".to_string(), 3)));
+}
+
 #[test]
 fn find_function_references_call_string() {
     let source = r#"
@@ -66,6 +163,133 @@ func _get_effective_music_volume() -> float:
     assert!(names.contains(&"_linear_to_db"));
 }
 
+#[test]
+fn find_function_references_dispatch_dict_string_values() {
+    let source = r#"
+var states = {
+    State.IDLE: "idle_tick",
+    State.RUN: "run_tick",
+}
+var labels = {"title": "menu_label"}
+
+func _process(_delta):
+    call(states[current])
+"#;
+    let refs = find_function_references(Path::new("a.gd"), source);
+    let names: Vec<_> = refs.iter().map(|r| r.0.as_str()).collect();
+    assert!(
+        names.contains(&"idle_tick"),
+        "dict value dispatched via call()"
+    );
+    assert!(names.contains(&"run_tick"));
+    assert!(
+        names.contains(&"menu_label"),
+        "any dict string value counts"
+    );
+
+    let without_dispatch = "var labels = {\"title\": \"menu_label\"}\nfunc f():\n    call(\"g\")\n";
+    let refs = find_function_references(Path::new("a.gd"), without_dispatch);
+    assert!(!refs.iter().any(|r| r.0 == "menu_label"));
+}
+
+#[test]
+fn find_function_references_bracket_string_call() {
+    let source = "func _ready():\n    actions[\"jump\"]()\n";
+    let refs = find_function_references(Path::new("a.gd"), source);
+    assert!(refs.iter().any(|r| r.0 == "jump"));
+}
+
+#[test]
+fn find_function_references_function_stored_inside_lambda() {
+    let source = r#"
+func _ready():
+    var run = func():
+        var handler = _real_handler
+        handler.call()
+    var inline = func(): var h = _inline_handler; h.call()
+    run.call()
+    inline.call()
+
+func _real_handler():
+    pass
+
+func _inline_handler():
+    pass
+"#;
+    let refs = find_function_references(Path::new("a.gd"), source);
+    let names: Vec<_> = refs.iter().map(|r| r.0.as_str()).collect();
+    assert!(
+        names.contains(&"_real_handler"),
+        "assignment inside a lambda body stores the function"
+    );
+    assert!(names.contains(&"_inline_handler"));
+}
+
+#[test]
+fn find_function_references_export_default_call() {
+    let source = r#"
+@export var speed := default_speed()
+@export_range(0, 10) var jump: float = default_jump()
+
+static func default_speed() -> float:
+    return 4.0
+"#;
+    let refs = find_function_references(Path::new("a.gd"), source);
+    let names: Vec<_> = refs.iter().map(|r| r.0.as_str()).collect();
+    assert!(names.contains(&"default_speed"), "call in @export default");
+    assert!(names.contains(&"default_jump"));
+}
+
+#[test]
+fn find_function_references_await_call() {
+    let source = r#"
+func _ready():
+    await load_level()
+    await obj.load_assets()
+    await $Loader.load_scene()
+"#;
+    let refs = find_function_references(Path::new("a.gd"), source);
+    let names: Vec<_> = refs.iter().map(|r| r.0.as_str()).collect();
+    assert!(names.contains(&"load_level"));
+    assert!(names.contains(&"load_assets"), "await obj.method() counts");
+    assert!(names.contains(&"load_scene"));
+    assert!(!names.contains(&"await"));
+}
+
+#[test]
+fn find_function_references_return_call() {
+    let source = r#"
+func total() -> int:
+    return compute()
+
+func total_paren() -> int:
+    return(compute_paren())
+"#;
+    let refs = find_function_references(Path::new("a.gd"), source);
+    let names: Vec<_> = refs.iter().map(|r| r.0.as_str()).collect();
+    assert!(names.contains(&"compute"), "return compute() should count");
+    assert!(
+        names.contains(&"compute_paren"),
+        "return(compute_paren()) should count"
+    );
+    assert!(!names.contains(&"return"), "keyword is not a reference");
+}
+
+#[test]
+fn find_function_references_inside_constructor_args() {
+    let source = r#"
+func _ready():
+    var w = Weapon.new(make_config())
+    var b = preload("res://bullet.gd").new(make_speed(), 2)
+"#;
+    let refs = find_function_references(Path::new("a.gd"), source);
+    assert!(
+        refs.contains(&("make_config".to_string(), 3)),
+        "call inside .new(...) args should count as reference"
+    );
+    assert!(refs.contains(&("make_speed".to_string(), 4)));
+}
+
 #[test]
 fn find_function_references_tween_method_callback() {
     let source = r#"
@@ -106,6 +330,19 @@ func _console_print(arg) -> void:
     );
 }
 
+#[test]
+fn find_function_references_var_initialized_with_function() {
+    let source = r#"extends Node
+@onready var cb := _on_something
+@export var typed: Callable = some_func
+var compact:Callable=other_func
+"#;
+    let refs = find_function_references(Path::new("a.gd"), source);
+    assert!(refs.contains(&("_on_something".to_string(), 2)));
+    assert!(refs.contains(&("some_func".to_string(), 3)));
+    assert!(refs.contains(&("other_func".to_string(), 4)));
+}
+
 #[test]
 fn test_find_tscn_references() {
     let source =
@@ -115,3 +352,154 @@ fn test_find_tscn_references() {
     assert!(names.contains(&"_on_quit_dialog_confirmed"));
     assert_eq!(refs.len(), 1);
 }
+
+#[test]
+fn test_find_tscn_property_references() {
+    let source =
+        "[node name=\"Turret\" type=\"Node2D\"]\ncallback_name = \"fire\"\nlabel = \"reload\"\n";
+    let props = vec!["callback_name".to_string()];
+    let refs = find_tscn_property_references(Path::new("turret.tscn"), source, &props);
+    assert_eq!(refs, vec![("fire".to_string(), 2)]);
+    assert!(find_tscn_property_references(Path::new("turret.tscn"), source, &[]).is_empty());
+}
+
+#[test]
+fn test_find_tres_references() {
+    let source = "[resource]\nmethod = \"_on_enter\"\non_exit = &\"_on_exit\"\ntitle = \"Main menu\"\nname = \"Idle\"\n";
+    let refs = find_tres_references(Path::new("idle.tres"), source);
+    assert_eq!(
+        refs,
+        vec![
+            ("_on_enter".to_string(), 2),
+            ("_on_exit".to_string(), 3),
+            ("Idle".to_string(), 5),
+        ]
+    );
+}
+
+#[test]
+fn find_const_aliases_class_and_preload() {
+    let source = r#"
+const W = Weapon
+const Scene: PackedScene = preload("res://scene.tscn")
+const MAX_SPEED = 10
+const lower = some_value
+"#;
+    let aliases = find_const_aliases(Path::new("a.gd"), source);
+    assert_eq!(
+        aliases,
+        vec![
+            ("W".to_string(), AliasTarget::Class("Weapon".to_string())),
+            (
+                "Scene".to_string(),
+                AliasTarget::Script("res://scene.tscn".into())
+            ),
+        ]
+    );
+}
+
+#[test]
+fn find_function_references_chained_call_credits_both() {
+    // Both the generic call rule and the `.method(` rule fire; each name is credited once.
+    let source = "func _ready():\n    get_weapon().reload()\n";
+    let refs = find_function_references(Path::new("player.gd"), source);
+    let count = |name: &str| refs.iter().filter(|r| r.0 == name).count();
+    assert!(refs.contains(&("get_weapon".to_string(), 2)));
+    assert!(refs.contains(&("reload".to_string(), 2)));
+    assert_eq!(count("get_weapon"), 1);
+    assert_eq!(count("reload"), 1);
+}
+
+#[test]
+fn find_function_references_method_call_after_cast() {
+    let source = "func _ready():\n    (get_node(\"X\") as Player).jump()\n";
+    let refs = find_function_references(Path::new("main.gd"), source);
+    assert!(refs.contains(&("jump".to_string(), 2)));
+}
+
+#[test]
+fn find_references_column_counts_chars() {
+    // "ż" is two bytes in UTF-8 but one column.
+    let source = "func _ready():\n    var ż = 1; never_called()\n    obj.never_called()\n";
+    let refs = find_references(Path::new("main.gd"), source);
+    let mut sites: Vec<_> = refs
+        .iter()
+        .filter(|r| r.name == "never_called")
+        .map(|r| (r.line, r.column))
+        .collect();
+    sites.sort();
+    sites.dedup();
+    assert_eq!(sites, vec![(2, 16), (3, 9)]);
+}
+
+#[test]
+fn find_function_references_call_group_method_name() {
+    let source = r#"extends Node
+func _hit(dmg):
+    get_tree().call_group("enemies", "take_damage", dmg)
+    get_tree().call_group_flags(SceneTree.GROUP_CALL_DEFERRED, &"ui", "refresh_hud")
+    get_tree().call_group(group_name, "freeze")
+"#;
+    let refs = find_function_references(Path::new("game.gd"), source);
+    assert!(refs.contains(&("take_damage".to_string(), 3)));
+    assert!(refs.contains(&("refresh_hud".to_string(), 4)));
+    assert!(refs.contains(&("freeze".to_string(), 5)));
+    assert!(
+        !refs
+            .iter()
+            .any(|(name, _)| name == "enemies" || name == "ui"),
+        "group names are not methods: {:?}",
+        refs
+    );
+}
+
+#[test]
+fn find_function_references_tween_callback_bound_and_callable() {
+    let source = r#"extends Node
+func _ready():
+    var tween = create_tween()
+    tween.tween_callback(_on_faded.bind(1.0))
+    tween.tween_callback(Callable(self, "_on_done"))
+    create_tween().tween_callback(self._on_chained.bind(2)).set_delay(0.5)
+    create_tween().tween_callback(Callable(self, "_on_wrapped").bind(3))
+"#;
+    let refs = find_references(Path::new("fx.gd"), source);
+    for (name, line) in [
+        ("_on_faded", 4),
+        ("_on_done", 5),
+        ("_on_chained", 6),
+        ("_on_wrapped", 7),
+    ] {
+        assert!(
+            refs.iter()
+                .any(|r| r.name == name && r.line == line && r.confidence == Confidence::Exact),
+            "{} not found as an exact reference: {:?}",
+            name,
+            refs
+        );
+    }
+}
+
+#[test]
+fn find_function_references_known_drops_loose_matches_on_unknown_names() {
+    let source = r#"extends Node
+func _ready():
+    var a = health
+    var b = on_hit
+    tween_method(set_volume, 0.0, 1.0, 0.5)
+    tween_method(speed, 0.0, 1.0, 0.5)
+    undefined_helper()
+"#;
+    let known: HashSet<String> = ["on_hit", "set_volume"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let refs = find_function_references_known(Path::new("a.gd"), source, &known);
+    assert!(refs.contains(&("on_hit".to_string(), 4)));
+    assert!(refs.contains(&("set_volume".to_string(), 5)));
+    assert!(!refs
+        .iter()
+        .any(|(name, _)| name == "health" || name == "speed"));
+    // Direct calls are exact and kept even when no script defines the name.
+    assert!(refs.contains(&("undefined_helper".to_string(), 7)));
+}
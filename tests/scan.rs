@@ -3,7 +3,9 @@
 mod common;
 use common::project;
 
-use gdcf::scanner::scan_directory;
+use gdcf::scanner::{
+    scan_directory, scan_directory_with_options, scan_directory_with_progress, ScanOptions,
+};
 
 #[test]
 fn scan_directory_with_debug_out() {
@@ -31,6 +33,32 @@ fn scan_directory_exclude_dirs() {
     assert!(!def_names.contains(&"only_in_plugin"));
 }
 
+#[test]
+fn scan_directory_ignore_file_marker_skips_definitions() {
+    let (_dir, root) = project(&[
+        ("main.gd", "extends Node\nfunc helper():\n    pass\n"),
+        (
+            "generated/api.gd",
+            "# gdcf-ignore-file\nextends Node\nfunc generated_dead():\n    pass\nfunc call_it():\n    helper()\n",
+        ),
+    ]);
+    let from_api = |result: &gdcf::ScanResult| {
+        result.references["helper"]
+            .iter()
+            .any(|site| site.path.ends_with("api.gd"))
+    };
+    let result = scan_directory(&root, &mut None, None);
+    let def_names: Vec<_> = result.definitions.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(def_names, vec!["helper"]);
+    assert!(from_api(&result), "calls from an ignored file still count");
+    let options = ScanOptions {
+        no_ignored_file_refs: true,
+        ..ScanOptions::default()
+    };
+    let result = scan_directory_with_options(&root, &mut None, &options);
+    assert!(!from_api(&result));
+}
+
 #[test]
 fn scan_directory_skips_unreadable_file() {
     let (_dir, root) = project(&[("ok.gd", "extends Node\nfunc _ready(): pass\n")]);
@@ -47,6 +75,12 @@ fn scan_directory_skips_unreadable_file() {
             .map(|d| d.file.file_name().unwrap().to_string_lossy().to_string())
             .collect();
         assert!(names.contains(&"ok.gd".to_string()));
+        // Root reads the file anyway; only check the report when it really is unreadable.
+        if std::fs::read(&bad).is_err() {
+            assert_eq!(result.skipped_files, vec![bad.canonicalize().unwrap()]);
+        } else {
+            assert!(result.skipped_files.is_empty());
+        }
     }
     #[cfg(not(unix))]
     {
@@ -54,3 +88,286 @@ fn scan_directory_skips_unreadable_file() {
         assert!(!result.definitions.is_empty());
     }
 }
+
+#[test]
+fn scan_directory_const_alias_resolves_to_class() {
+    let (_dir, root) = project(&[
+        (
+            "weapon.gd",
+            "class_name Weapon\nextends Node\nfunc fire():\n    pass\n",
+        ),
+        (
+            "main.gd",
+            r#"extends Node
+const W = Weapon
+const Bullet = preload("res://bullet.gd")
+func _ready():
+    W.fire()
+"#,
+        ),
+        ("bullet.gd", "extends Node\n"),
+    ]);
+    let root = root.canonicalize().unwrap();
+    let result = scan_directory(&root, &mut None, None);
+    let main = root.join("main.gd");
+    assert_eq!(
+        result.resolve_class(&main, "W"),
+        Some(root.join("weapon.gd").as_path())
+    );
+    assert_eq!(
+        result.resolve_class(&main, "Bullet"),
+        Some(root.join("bullet.gd").as_path())
+    );
+    assert_eq!(
+        result.resolve_class(&main, "Weapon"),
+        Some(root.join("weapon.gd").as_path())
+    );
+    assert_eq!(result.resolve_class(&main, "Unknown"), None);
+    let unused = gdcf::find_unused_functions(&root, Some(&result), None);
+    assert!(!unused.iter().any(|f| f.name == "fire"));
+}
+
+#[test]
+fn scan_directory_records_dynamic_lookup_calls() {
+    let (_dir, root) = project(&[(
+        "main.gd",
+        "extends Node\nvar data = {}\nfunc handler(): pass\nfunc _ready():\n    data.get(\"handler\").call()\n    data[\"handler\"].callv([])\n",
+    )]);
+    let result = scan_directory(&root, &mut None, None);
+    let lines: Vec<u32> = result.dynamic_calls.iter().map(|(_, l, _)| *l).collect();
+    assert_eq!(lines, vec![5, 6]);
+    assert!(result.dynamic_calls[0].0.ends_with("main.gd"));
+    // The key names a dictionary entry, not a function: `handler` stays unreferenced.
+    let handler_refs = result
+        .references
+        .get("handler")
+        .map(|sites| sites.iter().filter(|s| s.line != 3).count())
+        .unwrap_or(0);
+    assert_eq!(handler_refs, 0);
+}
+
+#[test]
+fn scan_directory_cache_reuses_unchanged_files() {
+    let (_dir, root) = project(&[
+        ("main.gd", "extends Node\nfunc _ready():\n    helper()\n"),
+        ("util.gd", "extends Node\nfunc helper():\n    pass\n"),
+    ]);
+    let cache = tempfile::tempdir().unwrap();
+    let options = ScanOptions {
+        exclude_dirs: vec!["addons".into()],
+        cache_dir: Some(cache.path().to_path_buf()),
+        ..ScanOptions::default()
+    };
+    let cold = scan_directory_with_options(&root, &mut None, &options);
+    assert_eq!(cold.parsed_files, 2);
+    let warm = scan_directory_with_options(&root, &mut None, &options);
+    assert_eq!(warm.parsed_files, 0);
+    assert_eq!(warm.definitions.len(), cold.definitions.len());
+    assert_eq!(warm.references, cold.references);
+
+    std::fs::write(
+        root.join("util.gd"),
+        "extends Node\nfunc helper():\n    return 1\n",
+    )
+    .unwrap();
+    let edited = scan_directory_with_options(&root, &mut None, &options);
+    assert_eq!(edited.parsed_files, 1);
+}
+
+#[test]
+fn scan_directory_cache_reparses_only_modified_file() {
+    let (_dir, root) = project(&[
+        ("main.gd", "extends Node\nfunc _ready():\n    helper()\n"),
+        ("util.gd", "extends Node\nfunc helper():\n    pass\n"),
+        (
+            "ui.tscn",
+            "[connection signal=\"pressed\" method=\"on_pressed\"]\n",
+        ),
+    ]);
+    let cache = tempfile::tempdir().unwrap();
+    let options = ScanOptions {
+        cache_dir: Some(cache.path().to_path_buf()),
+        ..ScanOptions::default()
+    };
+    assert_eq!(
+        scan_directory_with_options(&root, &mut None, &options).parsed_files,
+        3
+    );
+    // Rewritten with the same content: new mtime, same hash, so not parsed again.
+    std::fs::write(
+        root.join("main.gd"),
+        "extends Node\nfunc _ready():\n    helper()\n",
+    )
+    .unwrap();
+    assert_eq!(
+        scan_directory_with_options(&root, &mut None, &options).parsed_files,
+        0
+    );
+    std::fs::write(
+        root.join("util.gd"),
+        "extends Node\nfunc helper():\n    other()\nfunc other():\n    pass\n",
+    )
+    .unwrap();
+    let warm = scan_directory_with_options(&root, &mut None, &options);
+    assert_eq!(warm.parsed_files, 1, "only util.gd changed");
+    let cold = scan_directory_with_options(&root, &mut None, &ScanOptions::default());
+    assert_eq!(warm.definitions, cold.definitions);
+    assert_eq!(warm.references, cold.references);
+}
+
+#[test]
+fn scan_directory_cache_invalidated_by_exclude_change() {
+    let (_dir, root) = project(&[
+        ("main.gd", "extends Node\nfunc _ready():\n    pass\n"),
+        (
+            "vendor/lib.gd",
+            "extends Node\nfunc lib_func():\n    pass\n",
+        ),
+    ]);
+    let cache = tempfile::tempdir().unwrap();
+    let mut options = ScanOptions {
+        exclude_dirs: vec!["addons".into()],
+        cache_dir: Some(cache.path().to_path_buf()),
+        ..ScanOptions::default()
+    };
+    assert_eq!(
+        scan_directory_with_options(&root, &mut None, &options).parsed_files,
+        2
+    );
+    assert_eq!(
+        scan_directory_with_options(&root, &mut None, &options).parsed_files,
+        0
+    );
+    options.exclude_dirs = vec!["vendor".into()];
+    let rescanned = scan_directory_with_options(&root, &mut None, &options);
+    assert_eq!(
+        rescanned.parsed_files, 1,
+        "config change discards the cache, so main.gd is parsed again"
+    );
+}
+
+#[test]
+fn scan_directory_parallel_results_are_sorted_and_stable() {
+    let files: Vec<(String, String)> = (0..40)
+        .map(|i| {
+            (
+                format!("dir{}/s{:02}.gd", i % 4, i),
+                format!("func a{i}():\n    pass\nfunc b{i}():\n    a{i}()\n"),
+            )
+        })
+        .collect();
+    let refs: Vec<(&str, &str)> = files
+        .iter()
+        .map(|(p, c)| (p.as_str(), c.as_str()))
+        .collect();
+    let (_dir, root) = project(&refs);
+    let first = scan_directory(&root, &mut None, None);
+    let keys: Vec<_> = first
+        .definitions
+        .iter()
+        .map(|d| (d.file.clone(), d.line))
+        .collect();
+    let mut sorted = keys.clone();
+    sorted.sort();
+    assert_eq!(keys, sorted);
+    assert_eq!(keys.len(), 80);
+    for _ in 0..3 {
+        let again = scan_directory(&root, &mut None, None);
+        assert_eq!(again.definitions, first.definitions);
+        assert_eq!(again.references, first.references);
+    }
+}
+
+#[test]
+fn scan_directory_with_progress_reports_each_file() {
+    let (_dir, root) = project(&[
+        ("a.gd", "extends Node\nfunc a():\n    pass\n"),
+        ("sub/b.gd", "extends Node\nfunc b():\n    pass\n"),
+        ("main.tscn", "[node name=\"Main\" type=\"Node\"]\n"),
+    ]);
+    let mut calls = Vec::new();
+    let options = ScanOptions::default();
+    scan_directory_with_progress(&root, &mut None, &options, &mut |done, total| {
+        calls.push((done, total))
+    });
+    assert_eq!(calls, vec![(1, 3), (2, 3), (3, 3)]);
+}
+
+#[test]
+fn scan_directory_extensions_scan_extra_gdscript_files() {
+    let (_dir, root) = project(&[
+        ("main.gd", "extends Node\nfunc _ready():\n    pass\n"),
+        (
+            "tool.gdscript",
+            "extends Node\nfunc generated():\n    pass\n",
+        ),
+        ("main.tscn", "[node name=\"Main\" type=\"Node\"]\n"),
+    ]);
+    let names = |extensions: &[&str]| {
+        let options = ScanOptions {
+            extensions: extensions.iter().map(|e| e.to_string()).collect(),
+            ..ScanOptions::default()
+        };
+        let scan = scan_directory_with_options(&root, &mut None, &options);
+        let mut names: Vec<_> = scan.definitions.into_iter().map(|d| d.name).collect();
+        names.sort();
+        (names, scan.parsed_files)
+    };
+    assert_eq!(names(&[]), (vec!["_ready".to_string()], 2));
+    assert_eq!(
+        names(&["gd", ".GDScript"]),
+        (vec!["_ready".to_string(), "generated".to_string()], 3)
+    );
+    assert_eq!(
+        names(&["gdscript", "tscn"]),
+        (vec!["_ready".to_string(), "generated".to_string()], 3),
+        "`.gd` is always scanned and the .tscn still once, as a scene"
+    );
+}
+
+#[test]
+fn scan_directory_keeps_loose_references_only_for_defined_functions() {
+    let (_dir, root) = project(&[
+        (
+            "player.gd",
+            "extends Node\nvar health = 3\nfunc _ready():\n    var a = health\n    var b = on_hit\n    missing_call()\n",
+        ),
+        ("enemy.gd", "extends Node\nfunc on_hit():\n    pass\n"),
+    ]);
+    let scan = scan_directory(&root, &mut None, None);
+    assert!(scan.references.contains_key("on_hit"));
+    assert!(!scan.references.contains_key("health"));
+    assert!(
+        scan.references.contains_key("missing_call"),
+        "exact calls are kept without a definition"
+    );
+}
+
+#[test]
+fn scan_directory_reads_non_utf8_files_lossily() {
+    let (_dir, root) = project(&[(
+        "main.gd",
+        "extends Node\nfunc _ready():\n    legacy_helper()\n",
+    )]);
+    // Windows-1252 "é" (0xE9) in a comment: not valid UTF-8.
+    let mut legacy = b"extends Node\n# caf\xe9\nfunc legacy_helper():\n    pass\n".to_vec();
+    legacy.extend_from_slice(b"func unused_legacy():\n    pass\n");
+    std::fs::write(root.join("legacy.gd"), legacy).unwrap();
+    let mut debug = Vec::new();
+    let scan = {
+        let mut out: Option<&mut dyn std::io::Write> = Some(&mut debug);
+        scan_directory(&root, &mut out, None)
+    };
+    let legacy_defs: Vec<_> = scan
+        .definitions
+        .iter()
+        .filter(|d| d.file.ends_with("legacy.gd"))
+        .map(|d| (d.name.as_str(), d.line))
+        .collect();
+    assert_eq!(
+        legacy_defs,
+        vec![("legacy_helper", 3), ("unused_legacy", 5)]
+    );
+    let debug = String::from_utf8(debug).unwrap();
+    assert!(debug.contains("legacy.gd") && debug.contains("not valid UTF-8"));
+}